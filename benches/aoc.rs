@@ -1,30 +1,129 @@
+//! Benchmarks parsing and each part of every registered day, on the inputs
+//! in the local cache (`$AOC_INPUTS` or `inputs/`). Days without a cached
+//! input are skipped.
+//!
+//! Benchmarks are named `<year>/day<NN>/<parse|part1|part2>`, so e.g.
+//! `cargo bench -- 2024/day14` runs a single day. Parameters in
+//! `$AOC_PARAMS` apply to the days the filter selects, e.g.
+//! `AOC_PARAMS=width=11,height=7 cargo bench -- 2024/day14`.
+
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use regex::Regex;
 use std::env;
-use rudolf_rs;
-use solutions;
+use std::process;
+
+use solutions::input::{ input_dir, InputStore };
+use solutions::Params;
+
+// criterion's options that take a value as the next argument
+const VALUE_OPTIONS: &[&str] = &[
+    "-c", "--color", "-s", "--save-baseline", "-b", "--baseline", "--baseline-lenient",
+    "--load-baseline", "--sample-size", "--warm-up-time", "--measurement-time", "--nresamples",
+    "--noise-threshold", "--confidence-level", "--significance-level", "--profile-time",
+    "--plotting-backend", "--output-format", "--format",
+];
+
+// the name filter criterion was given, which we apply ourselves so unselected
+// days aren't parsed
+fn filter() -> Option<Regex> {
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        if VALUE_OPTIONS.contains(&arg.as_str()) {
+            args.next();
+        } else if !arg.starts_with('-') {
+            return Some(Regex::new(&arg).unwrap_or_else(|e| {
+                eprintln!("Invalid benchmark filter '{}': {}", arg, e);
+                process::exit(1);
+            }));
+        }
+    }
+
+    None
+}
+
+// e.g. AOC_PARAMS=width=11,height=7 to benchmark a day on an example; each day
+// rejects parameters it doesn't declare, so they need a filter naming the day
+fn params(filter: Option<&Regex>) -> Params {
+    let Ok(params) = env::var("AOC_PARAMS") else {
+        return Params::new();
+    };
+
+    if filter.is_none() {
+        eprintln!("AOC_PARAMS needs a filter naming the day to apply them to");
+        process::exit(1);
+    }
+
+    params.parse().unwrap_or_else(|e| {
+        eprintln!("Invalid AOC_PARAMS '{}': {}", params, e);
+        process::exit(1);
+    })
+}
 
 fn criterion_benchmark(c: &mut Criterion) {
-    let year = 2024;
-    let day = 21;
-
-    let session_key = env::var("AOC_SESSION").unwrap();
-    let client = rudolf_rs::Client::new(String::from(session_key));
-    let input = client.get_cached(year, day).unwrap();
-    let solution = solutions::get_solution(year as usize, day as usize);
-
-    c.bench_function(
-        &format!("year{}day{}part1", year, day),
-        |b| {
-            b.iter(|| solution.part1(black_box(&input)))
+    let store = InputStore::new(input_dir());
+    let filter = filter();
+    let params = params(filter.as_ref());
+
+    for puzzle in solutions::puzzles() {
+        let (year, day) = (puzzle.year, puzzle.day);
+        let group_name = format!("{}/day{:02}", year, day);
+
+        let selected = ["parse", "part1", "part2"].iter().any(|step| {
+            let id = format!("{}/{}", group_name, step);
+            filter.as_ref().is_none_or(|filter| filter.is_match(&id))
+        });
+
+        if !selected {
+            continue;
         }
-    );
 
-    c.bench_function(
-        &format!("year{}day{}part2", year, day),
-        |b| {
-            b.iter(|| solution.part2(black_box(&input)))
+        let input = match store.get_cached(year, day) {
+            Ok(Some(input)) => input,
+            Ok(None) => {
+                eprintln!("Skipping {}: no cached input", group_name);
+                continue;
+            },
+            Err(e) => {
+                eprintln!("Skipping {}: {}", group_name, e);
+                continue;
+            },
+        };
+
+        // report bad input once instead of panicking mid-benchmark
+        let prepared = match puzzle.solver.prepare(&input, &params) {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                eprintln!("Skipping {}: {}", group_name, e.in_day(day));
+                continue;
+            },
+        };
+
+        let mut group = c.benchmark_group(&group_name);
+
+        // days whose parts parse the input have nothing to time here
+        if puzzle.solver.parses_input() {
+            group.bench_function("parse", |b| {
+                b.iter(|| puzzle.solver.prepare(black_box(&input), &params))
+            });
         }
-    );
+
+        for (part, name) in [(1, "part1"), (2, "part2")] {
+            let solve = || match part {
+                1 => prepared.part1(),
+                _ => prepared.part2(),
+            };
+
+            if let Err(e) = solve() {
+                eprintln!("Skipping {}/{}: {}", group_name, name, e.in_puzzle(day, part));
+                continue;
+            }
+
+            group.bench_function(name, |b| b.iter(solve));
+        }
+
+        group.finish();
+    }
 }
 
 criterion_group!{
@@ -0,0 +1,242 @@
+use std::cmp::Ordering;
+use std::convert::Infallible;
+use std::fmt::{ self, Display };
+use std::hash::{ Hash, Hasher };
+use std::str::FromStr;
+
+use num::BigInt;
+
+use crate::json::Value;
+
+/// A puzzle answer.
+///
+/// Answers compare equal when they display the same, so an answer read back
+/// from text as a string still matches the integer a solver returned.
+#[derive(Clone, Debug)]
+pub enum Answer {
+    Integer(i128),
+    BigInteger(BigInt),
+    String(String),
+    Coordinate(i64, i64),
+}
+
+impl Answer {
+    /// The answer as JSON: a number, a string or an `[x, y]` array.
+    pub fn to_json(&self) -> Value {
+        match self {
+            Self::Integer(n) => (*n).into(),
+            Self::BigInteger(n) => Value::Number(n.to_string()),
+            Self::String(s) => s.as_str().into(),
+            Self::Coordinate(x, y) => Value::Array(vec![(*x).into(), (*y).into()]),
+        }
+    }
+
+    pub fn from_json(value: &Value) -> Option<Self> {
+        match value {
+            Value::Number(n) => match n.parse() {
+                Ok(answer @ (Self::Integer(_) | Self::BigInteger(_))) => Some(answer),
+                _ => None,
+            },
+            Value::String(s) => Some(Self::String(s.clone())),
+            Value::Array(values) => match values.as_slice() {
+                [x, y] => Some(Self::Coordinate(x.as_i64()?, y.as_i64()?)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn to_big_int(&self) -> Option<BigInt> {
+        match self {
+            Self::Integer(n) => Some((*n).into()),
+            Self::BigInteger(n) => Some(n.clone()),
+            _ => None,
+        }
+    }
+
+    /// Compares two numeric answers, or `None` unless both are numbers.
+    pub fn cmp_numeric(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Integer(a), Self::Integer(b)) => Some(a.cmp(b)),
+            _ => Some(self.to_big_int()?.cmp(&other.to_big_int()?)),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(n) => write!(f, "{}", n),
+            Self::BigInteger(n) => write!(f, "{}", n),
+            Self::String(s) => write!(f, "{}", s),
+            Self::Coordinate(x, y) => write!(f, "{},{}", x, y),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Integer(a), Self::Integer(b)) => a == b,
+            (Self::Coordinate(ax, ay), Self::Coordinate(bx, by)) => {
+                (ax, ay) == (bx, by)
+            },
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl Hash for Answer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_string().hash(state);
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    /// Reads back a displayed answer, choosing the narrowest variant.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if let Ok(n) = s.parse() {
+            return Ok(Self::Integer(n));
+        }
+
+        if let Ok(n) = s.parse() {
+            return Ok(Self::BigInteger(n));
+        }
+
+        if let Some((x, y)) = s.split_once(',') {
+            if let (Ok(x), Ok(y)) = (x.parse(), y.parse()) {
+                return Ok(Self::Coordinate(x, y));
+            }
+        }
+
+        Ok(Self::String(s.to_string()))
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Self::Integer(n.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Self::Integer(n as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(n: isize) -> Self {
+        Self::Integer(n as i128)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        match i128::try_from(n) {
+            Ok(n) => Self::Integer(n),
+            Err(_) => Self::BigInteger(n.into()),
+        }
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        match i128::try_from(&n) {
+            Ok(n) => Self::Integer(n),
+            Err(_) => Self::BigInteger(n),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Self::String(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Self::String(s.to_string())
+    }
+}
+
+impl From<(i64, i64)> for Answer {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self::Coordinate(x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use num::BigInt;
+
+    use super::Answer;
+
+    #[test]
+    fn test_parse() {
+        let parse = |s: &str| s.parse::<Answer>().unwrap();
+
+        assert!(matches!(parse(" 42\n"), Answer::Integer(42)));
+        assert!(matches!(parse("6,1"), Answer::Coordinate(6, 1)));
+        assert!(matches!(parse("4,6,3"), Answer::String(_)));
+        assert!(matches!(
+            parse("1000000000000000000000000000000000000000"),
+            Answer::BigInteger(_)
+        ));
+    }
+
+    #[test]
+    fn test_eq() {
+        let big: BigInt = "123456789012345678901234567890".parse().unwrap();
+
+        assert_eq!(Answer::from(42usize), Answer::from(42i64));
+        assert_eq!(Answer::from(big.clone()), Answer::Integer(big.try_into().unwrap()));
+        assert_eq!(Answer::from("4,6"), Answer::from((4, 6)));
+        assert_ne!(Answer::from(42u64), Answer::from("042"));
+    }
+
+    #[test]
+    fn test_cmp_numeric() {
+        let big = Answer::BigInteger("1".repeat(50).parse().unwrap());
+
+        assert_eq!(Answer::from(2u8).cmp_numeric(&Answer::from(10i64)), Some(Ordering::Less));
+        assert_eq!(big.cmp_numeric(&Answer::from(-1i64)), Some(Ordering::Greater));
+        assert_eq!(Answer::from("abc").cmp_numeric(&Answer::from(1u8)), None);
+        assert_eq!(Answer::from((1, 2)).cmp_numeric(&Answer::from((1, 2))), None);
+    }
+
+    #[test]
+    fn test_json() {
+        let answers = [
+            Answer::from(-7i64),
+            Answer::BigInteger("1".repeat(50).parse().unwrap()),
+            Answer::from("4,6,3,5\n"),
+            Answer::from((6, 1)),
+        ];
+
+        let json: Vec<_> = answers.iter().map(|a| a.to_json().to_string()).collect();
+
+        assert_eq!(json, ["-7", &"1".repeat(50), "\"4,6,3,5\\n\"", "[6,1]"]);
+
+        for answer in answers {
+            assert_eq!(Answer::from_json(&answer.to_json()), Some(answer));
+        }
+    }
+}
@@ -0,0 +1,395 @@
+use std::path::PathBuf;
+
+use solutions::{ input, Params, SolveError };
+
+pub const USAGE: &str = "\
+Usage: aoc <command> [options]
+
+Commands:
+    run <year> <day>       solve a day (also just `aoc <year> <day>`)
+    all <year>             solve every registered day of a year
+    list [year]            list the registered days
+    years                  list the years with registered days
+    verify [year [day]]    check cached inputs against the answers file
+    examples [year [day]]  check the examples in fixtures/
+    watch <year> <day>     rerun a day's examples and input whenever its
+                           source, examples or input change
+    bench [year]           time every day with a cached input and compare
+                           against the benchmark history
+    submit <year> <day> <part>
+                           solve a part and submit the answer, unless
+                           earlier guesses rule it out
+    guesses <year> <day>   show the answers submitted and the bounds they
+                           imply
+    report [year]          show the stars earned and times of each day
+    new <year> <day>       generate and register a day from the template
+    inputs list [year]     list the cached inputs, checking their hashes
+    inputs import <year> <day> <path|->
+                           cache an input from a file or stdin
+    inputs clear <year> [day]
+                           remove cached inputs
+    help                   show this message
+
+Options:
+    --part <1|2>           only solve one part (run)
+    -p, --param <name=value[,name=value...]>
+                           override puzzle parameters (run)
+    -i, --input <path|->   solve a file, or stdin, instead of the puzzle
+                           input (run), or the example to test against (new)
+    --cache-dir <dir>      where puzzle inputs are cached (default
+                           $AOC_INPUTS or inputs/)
+    --time                 report how long parsing and each part took (run)
+    --repeat <n>           solve n times, reporting the min/median times
+                           (run, all)
+    --format <text|json|markdown>
+                           print text, or a JSON object per part (run,
+                           all), or a Markdown table (report)
+    --readme               write the report into README.md (report)
+    -v, --verbose          show solver diagnostics on stderr
+    --save                 record the timings in the history (bench)
+    --baseline <commit>    compare against the timings recorded for a
+                           commit, rather than the latest (bench)
+    --threshold <percent>  how much slower counts as a regression (bench,
+                           default 10)
+    --title <title>        the puzzle's title (new, default Day <day>)
+
+Puzzle inputs are read from the cache, or downloaded with $AOC_SESSION and
+cached. Submitting runs curl, and submissions are recorded in
+$AOC_SUBMISSIONS or submissions.jsonl.
+
+Exit codes: 1 verification failed, 2 bad arguments, 3 missing input,
+4 solver error.";
+
+pub enum Command {
+    Run { year: u16, day: u8 },
+    All { year: u16 },
+    List { year: Option<u16> },
+    Years,
+    Verify { year: Option<u16>, day: Option<u8> },
+    Examples { year: Option<u16>, day: Option<u8> },
+    Watch { year: u16, day: u8 },
+    Bench { year: Option<u16> },
+    Report { year: Option<u16> },
+    Submit { year: u16, day: u8, part: u8 },
+    Guesses { year: u16, day: u8 },
+    New { year: u16, day: u8 },
+    Inputs(Inputs),
+    Help,
+}
+
+pub enum Inputs {
+    List { year: Option<u16> },
+    Import { year: u16, day: u8, path: String },
+    Clear { year: u16, day: Option<u8> },
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Format {
+    Text,
+    /// a line of JSON per part, with the answer or error and the times
+    Json,
+    Markdown,
+}
+
+pub struct Options {
+    pub params: Params,
+    /// a file to solve instead of the puzzle input, or `-` for stdin
+    pub input: Option<String>,
+    pub cache_dir: PathBuf,
+    pub part: Option<u8>,
+    pub time: bool,
+    pub repeat: usize,
+    pub format: Format,
+    pub verbose: bool,
+    pub save: bool,
+    pub baseline: Option<String>,
+    pub threshold: f64,
+    pub title: Option<String>,
+    pub readme: bool,
+}
+
+impl Options {
+    /// The parts to solve, both unless `--part` was given.
+    pub fn parts(&self) -> Vec<u8> {
+        self.part.map_or(vec![1, 2], |part| vec![part])
+    }
+}
+
+pub struct Arguments {
+    pub command: Command,
+    pub options: Options,
+}
+
+fn year(arg: &str) -> Result<u16, String> {
+    arg.parse().map_err(|_| format!("Invalid year '{}'", arg))
+}
+
+fn part(arg: &str) -> Result<u8, String> {
+    match arg {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("Invalid part '{}'", arg)),
+    }
+}
+
+fn day(arg: &str) -> Result<u8, String> {
+    match arg.parse() {
+        Ok(day @ 1..=25) => Ok(day),
+        _ => Err(format!("Invalid day '{}'", arg)),
+    }
+}
+
+impl Arguments {
+    /// Parses the arguments following the program name.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut positional = Vec::new();
+        let mut options = Options {
+            params: Params::new(),
+            input: None,
+            cache_dir: input::input_dir(),
+            part: None,
+            time: false,
+            repeat: 1,
+            format: Format::Text,
+            verbose: false,
+            save: false,
+            baseline: None,
+            threshold: 10.0,
+            title: None,
+            readme: false,
+        };
+
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| args
+                .next()
+                .ok_or_else(|| format!("Missing value for {}", name));
+
+            match arg.as_str() {
+                "-p" | "--param" => {
+                    let params: Params = value(&arg)?
+                        .parse()
+                        .map_err(|e: SolveError| e.message().to_string())?;
+
+                    for (name, value) in params.iter() {
+                        options.params.set(name, value);
+                    }
+                },
+                "-i" | "--input" => options.input = Some(value(&arg)?),
+                "--cache-dir" => options.cache_dir = value(&arg)?.into(),
+                "--part" => options.part = Some(part(&value(&arg)?)?),
+                "--time" => options.time = true,
+                "--repeat" => {
+                    let repeat = value(&arg)?;
+                    options.repeat = match repeat.parse() {
+                        Ok(n) if n > 0 => n,
+                        _ => return Err(format!("Invalid repeat count '{}'", repeat)),
+                    };
+                },
+                "--format" => {
+                    options.format = match value(&arg)?.as_str() {
+                        "text" => Format::Text,
+                        "json" => Format::Json,
+                        "markdown" => Format::Markdown,
+                        format => return Err(format!("Unknown format '{}'", format)),
+                    };
+                },
+                "-v" | "--verbose" => options.verbose = true,
+                "--save" => options.save = true,
+                "--baseline" => options.baseline = Some(value(&arg)?),
+                "--threshold" => {
+                    let threshold = value(&arg)?;
+                    options.threshold = match threshold.trim_end_matches('%').parse() {
+                        Ok(t) if t >= 0.0 => t,
+                        _ => return Err(format!("Invalid threshold '{}'", threshold)),
+                    };
+                },
+                "--title" => options.title = Some(value(&arg)?),
+                "--readme" => options.readme = true,
+                "-h" | "--help" => positional.insert(0, "help".to_string()),
+                _ if arg.starts_with('-') && arg != "-" => {
+                    return Err(format!("Unknown option '{}'", arg));
+                },
+                _ => positional.push(arg),
+            }
+        }
+
+        let positional: Vec<_> = positional.iter().map(String::as_str).collect();
+
+        let command = match positional.as_slice() {
+            ["help", ..] | [] => Command::Help,
+            ["run", y, d] => Command::Run { year: year(y)?, day: day(d)? },
+            [y, d] if y.parse::<u16>().is_ok() => {
+                Command::Run { year: year(y)?, day: day(d)? }
+            },
+            ["all", y] => Command::All { year: year(y)? },
+            ["list"] => Command::List { year: None },
+            ["list", y] => Command::List { year: Some(year(y)?) },
+            ["years"] => Command::Years,
+            ["verify", rest @ ..] if rest.len() <= 2 => Command::Verify {
+                year: rest.first().map(|y| year(y)).transpose()?,
+                day: rest.get(1).map(|d| day(d)).transpose()?,
+            },
+            ["examples", rest @ ..] if rest.len() <= 2 => Command::Examples {
+                year: rest.first().map(|y| year(y)).transpose()?,
+                day: rest.get(1).map(|d| day(d)).transpose()?,
+            },
+            ["watch", y, d] => Command::Watch { year: year(y)?, day: day(d)? },
+            ["bench"] => Command::Bench { year: None },
+            ["bench", y] => Command::Bench { year: Some(year(y)?) },
+            ["report"] => Command::Report { year: None },
+            ["report", y] => Command::Report { year: Some(year(y)?) },
+            ["submit", y, d, p] => Command::Submit { year: year(y)?, day: day(d)?, part: part(p)? },
+            ["guesses", y, d] => Command::Guesses { year: year(y)?, day: day(d)? },
+            ["new", y, d] => Command::New { year: year(y)?, day: day(d)? },
+            ["inputs", "list"] => Command::Inputs(Inputs::List { year: None }),
+            ["inputs", "list", y] => Command::Inputs(Inputs::List { year: Some(year(y)?) }),
+            ["inputs", "import", y, d, path] => Command::Inputs(Inputs::Import {
+                year: year(y)?,
+                day: day(d)?,
+                path: path.to_string(),
+            }),
+            ["inputs", "clear", y, rest @ ..] if rest.len() <= 1 => Command::Inputs(Inputs::Clear {
+                year: year(y)?,
+                day: rest.first().map(|d| day(d)).transpose()?,
+            }),
+            ["inputs", ..] => return Err("Usage: aoc inputs <list|import|clear> ...".to_string()),
+            [command, ..] if [
+                "run", "all", "list", "years", "verify", "examples", "watch", "bench", "report",
+                "submit", "guesses", "new",
+            ].contains(command) => {
+                return Err(format!("Wrong number of arguments to {}", command));
+            },
+            [command, ..] => return Err(format!("Unknown command '{}'", command)),
+        };
+
+        let is_run = matches!(command, Command::Run { .. });
+        let is_all = matches!(command, Command::All { .. });
+        let is_bench = matches!(command, Command::Bench { .. });
+        let is_new = matches!(command, Command::New { .. });
+        let is_report = matches!(command, Command::Report { .. });
+
+        if !is_run && (options.part.is_some() || options.time) {
+            return Err("--part and --time only apply to run".to_string());
+        }
+
+        if !is_run && !is_new && options.input.is_some() {
+            return Err("--input only applies to run and new".to_string());
+        }
+
+        if !is_new && options.title.is_some() {
+            return Err("--title only applies to new".to_string());
+        }
+
+        match options.format {
+            Format::Json if !is_run && !is_all => {
+                return Err("--format json only applies to run and all".to_string());
+            },
+            Format::Markdown if !is_report => {
+                return Err("--format markdown only applies to report".to_string());
+            },
+            _ => (),
+        }
+
+        if !is_report && options.readme {
+            return Err("--readme only applies to report".to_string());
+        }
+
+        if !is_run && !is_all && !is_bench && options.repeat > 1 {
+            return Err("--repeat only applies to run, all and bench".to_string());
+        }
+
+        if !is_bench && (options.save || options.baseline.is_some()) {
+            return Err("--save and --baseline only apply to bench".to_string());
+        }
+
+        if !is_run && !options.params.is_empty() {
+            return Err("--param only applies to run".to_string());
+        }
+
+        Ok(Arguments { command, options })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Arguments, Command, Format, Inputs};
+
+    fn parse(args: &str) -> Result<Arguments, String> {
+        Arguments::parse(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse() {
+        let args = parse("run 2024 14 --part 2 -p width=11,height=7 -i - --repeat 3").unwrap();
+
+        assert!(matches!(args.command, Command::Run { year: 2024, day: 14 }));
+        assert_eq!(args.options.parts(), [2]);
+        assert_eq!(args.options.params.to_string(), "height=7,width=11");
+        assert_eq!(args.options.input.as_deref(), Some("-"));
+        assert_eq!(args.options.repeat, 3);
+
+        assert!(matches!(parse("2024 1").unwrap().command, Command::Run { .. }));
+        assert_eq!(parse("all 2024 --format json").unwrap().options.format, Format::Json);
+        assert!(matches!(parse("list").unwrap().command, Command::List { year: None }));
+        assert!(matches!(parse("years").unwrap().command, Command::Years));
+        assert!(matches!(
+            parse("examples 2024 3").unwrap().command,
+            Command::Examples { year: Some(2024), day: Some(3) }
+        ));
+        assert!(matches!(
+            parse("verify 2024").unwrap().command,
+            Command::Verify { year: Some(2024), day: None }
+        ));
+        assert!(matches!(parse("").unwrap().command, Command::Help));
+
+        let args = parse("bench --baseline abc123 --threshold 5% --repeat 10").unwrap();
+        assert!(matches!(args.command, Command::Bench { year: None }));
+        assert_eq!(args.options.threshold, 5.0);
+
+        let args = parse("report 2024 --format markdown --readme").unwrap();
+        assert!(matches!(args.command, Command::Report { year: Some(2024) }));
+        assert!(args.options.readme);
+
+        assert!(matches!(
+            parse("submit 2024 1 2").unwrap().command,
+            Command::Submit { year: 2024, day: 1, part: 2 }
+        ));
+
+        let args = parse("new 2025 3 -i example.txt --title Lobby").unwrap();
+        assert!(matches!(args.command, Command::New { year: 2025, day: 3 }));
+        assert_eq!(args.options.title.as_deref(), Some("Lobby"));
+
+        assert!(matches!(
+            parse("inputs import 2024 1 -").unwrap().command,
+            Command::Inputs(Inputs::Import { year: 2024, day: 1, .. })
+        ));
+        assert!(matches!(
+            parse("inputs clear 2024").unwrap().command,
+            Command::Inputs(Inputs::Clear { year: 2024, day: None })
+        ));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("run 2024").is_err());
+        assert!(parse("run 2024 26").is_err());
+        assert!(parse("all 2024 --part 1").is_err());
+        assert!(parse("frobnicate").is_err());
+        assert!(parse("2024 1 --part 3").is_err());
+        assert!(parse("2024 1 --quiet").is_err());
+        assert!(parse("all 2024 --repeat 0").is_err());
+        assert!(parse("list --repeat 2").is_err());
+        assert!(parse("2024 1 --format yaml").is_err());
+        assert!(parse("all 2024 --save").is_err());
+        assert!(parse("run 2024 1 --title Lobby").is_err());
+        assert!(parse("all 2024 -i example.txt").is_err());
+        assert!(parse("inputs clear").is_err());
+        assert!(parse("submit 2024 1").is_err());
+        assert!(parse("report --format json").is_err());
+        assert!(parse("all 2024 --format markdown").is_err());
+        assert!(parse("submit 2024 1 3").is_err());
+        assert!(parse("inputs fetch 2024").is_err());
+    }
+}
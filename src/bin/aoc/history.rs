@@ -0,0 +1,321 @@
+//! Benchmark timings recorded per git commit, so a run can be compared
+//! against an earlier one.
+//!
+//! The history is a JSON lines file with an entry per recorded run:
+//!
+//! ```text
+//! {"version":1,"commit":"9b08666","dirty":false,"timestamp":1734000000,
+//!  "repeat":10,"times":[{"year":2024,"day":1,"step":"parse","ns":51234},...]}
+//! ```
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::{ self, Display };
+use std::fs::{ self, OpenOptions };
+use std::io::Write;
+use std::path::{ Path, PathBuf };
+use std::process::Command;
+use std::time::{ Duration, SystemTime };
+
+use solutions::json::Value;
+
+const VERSION: i64 = 1;
+
+/// Changes to times shorter than this are measurement noise.
+const NOISE_FLOOR: Duration = Duration::from_micros(1);
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum Step {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Step {
+    pub fn part(part: u8) -> Self {
+        match part {
+            1 => Step::Part1,
+            _ => Step::Part2,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Step::Parse => "parse",
+            Step::Part1 => "part1",
+            Step::Part2 => "part2",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [Step::Parse, Step::Part1, Step::Part2]
+            .into_iter()
+            .find(|step| step.name() == name)
+    }
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// The median time of each step of each day in one benchmark run.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Entry {
+    pub commit: String,
+    /// whether the working tree had uncommitted changes
+    pub dirty: bool,
+    pub timestamp: u64,
+    pub repeat: usize,
+    pub times: BTreeMap<(u16, u8, Step), Duration>,
+}
+
+impl Entry {
+    /// An entry for the current commit of the git checkout, if any.
+    pub fn new(repeat: usize) -> Self {
+        let git = |args: &[&str]| Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |time| time.as_secs());
+
+        Entry {
+            commit: git(&["rev-parse", "--short", "HEAD"]).unwrap_or_default(),
+            dirty: git(&["status", "--porcelain"]).is_some_and(|status| !status.is_empty()),
+            timestamp,
+            repeat,
+            times: BTreeMap::new(),
+        }
+    }
+
+    /// The commit, marked if the working tree was dirty.
+    pub fn label(&self) -> String {
+        match (self.commit.as_str(), self.dirty) {
+            ("", _) => "unknown commit".to_string(),
+            (commit, true) => format!("{}-dirty", commit),
+            (commit, false) => commit.to_string(),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        let times = self
+            .times
+            .iter()
+            .map(|(&(year, day, step), time)| Value::Object(vec![
+                ("year".to_string(), year.into()),
+                ("day".to_string(), day.into()),
+                ("step".to_string(), step.name().into()),
+                ("ns".to_string(), time.as_nanos().into()),
+            ]))
+            .collect();
+
+        Value::Object(vec![
+            ("version".to_string(), VERSION.into()),
+            ("commit".to_string(), self.commit.as_str().into()),
+            ("dirty".to_string(), self.dirty.into()),
+            ("timestamp".to_string(), self.timestamp.into()),
+            ("repeat".to_string(), self.repeat.into()),
+            ("times".to_string(), Value::Array(times)),
+        ])
+    }
+
+    fn from_json(value: &Value) -> Result<Self, String> {
+        let field = |name: &str| value
+            .get(name)
+            .ok_or_else(|| format!("Missing field '{}'", name));
+
+        let int = |value: &Value, name: &str| value
+            .get(name)
+            .and_then(Value::as_i64)
+            .ok_or_else(|| format!("Missing or invalid field '{}'", name));
+
+        let version = int(value, "version")?;
+
+        if version != VERSION {
+            return Err(format!("Unsupported version {}", version));
+        }
+
+        let Value::Array(times_json) = field("times")? else {
+            return Err("Field 'times' is not an array".to_string());
+        };
+
+        let mut times = BTreeMap::new();
+
+        for time in times_json {
+            let step = time
+                .get("step")
+                .and_then(Value::as_str)
+                .and_then(Step::from_name)
+                .ok_or("Missing or invalid field 'step'")?;
+
+            let key = (
+                int(time, "year")?.try_into().map_err(|_| "Invalid year")?,
+                int(time, "day")?.try_into().map_err(|_| "Invalid day")?,
+                step,
+            );
+
+            let ns = int(time, "ns")?.try_into().map_err(|_| "Invalid time")?;
+            times.insert(key, Duration::from_nanos(ns));
+        }
+
+        Ok(Entry {
+            commit: field("commit")?.as_str().unwrap_or_default().to_string(),
+            dirty: matches!(field("dirty")?, Value::Bool(true)),
+            timestamp: int(value, "timestamp")?.try_into().unwrap_or_default(),
+            repeat: int(value, "repeat")?.try_into().unwrap_or(1),
+            times,
+        })
+    }
+}
+
+/// The history file, `$AOC_BENCH_HISTORY` or `bench-history.jsonl` in the
+/// working directory.
+pub fn path() -> PathBuf {
+    env::var_os("AOC_BENCH_HISTORY")
+        .map_or_else(|| PathBuf::from("bench-history.jsonl"), PathBuf::from)
+}
+
+/// Loads every recorded entry, oldest first.
+pub fn load(path: &Path) -> Result<Vec<Entry>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let history = fs::read_to_string(path)
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    history
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| line
+            .parse()
+            .and_then(|value| Entry::from_json(&value))
+            .map_err(|e| format!("{}:{}: {}", path.display(), i + 1, e))
+        )
+        .collect()
+}
+
+pub fn append(path: &Path, entry: &Entry) -> Result<(), String> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    writeln!(file, "{}", entry.to_json()).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// The latest entry recorded for a commit starting with `commit`, or the
+/// latest entry of all if no commit is given.
+pub fn baseline<'a>(history: &'a [Entry], commit: Option<&str>) -> Option<&'a Entry> {
+    history
+        .iter()
+        .rev()
+        .find(|entry| commit.is_none_or(|commit| entry.commit.starts_with(commit)))
+}
+
+pub struct Comparison {
+    pub key: (u16, u8, Step),
+    pub baseline: Duration,
+    pub current: Duration,
+    /// whether the current time is slower than the baseline by more than the
+    /// threshold
+    pub regression: bool,
+}
+
+impl Comparison {
+    /// The relative change in percent, or `None` if the baseline step took
+    /// no measurable time, so there is nothing to compare against.
+    pub fn change(&self) -> Option<f64> {
+        if self.baseline.is_zero() {
+            return None;
+        }
+
+        let baseline = self.baseline.as_secs_f64();
+        Some((self.current.as_secs_f64() - baseline) / baseline * 100.0)
+    }
+}
+
+/// Compares the steps timed in both entries, flagging those more than
+/// `threshold` percent slower than the baseline.
+pub fn compare(baseline: &Entry, current: &Entry, threshold: f64) -> Vec<Comparison> {
+    current
+        .times
+        .iter()
+        .filter_map(|(key, &current)| {
+            let &baseline = baseline.times.get(key)?;
+            let slower = current.saturating_sub(baseline);
+
+            let regression = !baseline.is_zero()
+                && slower > NOISE_FLOOR
+                && current.as_secs_f64() > baseline.as_secs_f64() * (1.0 + threshold / 100.0);
+
+            Some(Comparison { key: *key, baseline, current, regression })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    use super::{baseline, compare, Entry, Step};
+
+    fn entry(commit: &str, times: &[(u8, Step, u64)]) -> Entry {
+        let times = times
+            .iter()
+            .map(|&(day, step, us)| ((2024, day, step), Duration::from_micros(us)))
+            .collect::<BTreeMap<_, _>>();
+
+        Entry { commit: commit.to_string(), dirty: false, timestamp: 0, repeat: 1, times }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let entry = entry("abc1234", &[(1, Step::Parse, 50), (1, Step::Part2, 7)]);
+        let json = entry.to_json().to_string();
+
+        assert_eq!(Entry::from_json(&json.parse().unwrap()), Ok(entry));
+
+        let newer = json.replace("\"version\":1", "\"version\":2");
+        assert!(Entry::from_json(&newer.parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let history = [
+            entry("abc1234", &[(1, Step::Parse, 100), (1, Step::Part1, 100)]),
+            entry("def5678", &[(1, Step::Parse, 50), (1, Step::Part1, 100)]),
+        ];
+        let current = entry("", &[
+            (1, Step::Parse, 105),
+            (1, Step::Part1, 120),
+            (2, Step::Part1, 1),
+        ]);
+
+        assert_eq!(baseline(&history, None).unwrap().commit, "def5678");
+
+        let base = baseline(&history, Some("abc")).unwrap();
+        let regressions: Vec<_> = compare(base, &current, 10.0)
+            .into_iter()
+            .filter(|c| c.regression)
+            .map(|c| c.key)
+            .collect();
+
+        assert_eq!(regressions, [(2024, 1, Step::Part1)]);
+
+        let zero = entry("", &[(1, Step::Parse, 0)]);
+        let comparisons = compare(&zero, &current, 10.0);
+        assert_eq!(comparisons.len(), 1);
+        assert!(!comparisons[0].regression);
+        assert_eq!(comparisons[0].change(), None);
+    }
+}
@@ -0,0 +1,696 @@
+mod args;
+mod history;
+mod output;
+mod report;
+mod scaffold;
+mod timing;
+mod watch;
+
+use std::env;
+use std::fs;
+use std::io::{ self, Read };
+use std::path::Path;
+use std::process;
+
+use solutions::input::{ InputStore, Integrity, RudolfFetcher };
+use solutions::submit::{ HttpBackend, Submissions, Verdict };
+use solutions::{ fixtures, Answers, Outcome, Puzzle };
+
+use args::{ Arguments, Command, Format, Inputs, Options, USAGE };
+use history::{ Entry, Step };
+use output::DayResult;
+use timing::{ format_duration, run_day, DayRun };
+
+const EXIT_FAILED: i32 = 1;
+const EXIT_USAGE: i32 = 2;
+const EXIT_NO_INPUT: i32 = 3;
+const EXIT_SOLVER: i32 = 4;
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!();
+    eprintln!("{}", USAGE);
+    process::exit(EXIT_USAGE);
+}
+
+fn unknown_year(year: u16) -> String {
+    let years: Vec<_> = solutions::years().map(|year| year.year.to_string()).collect();
+    format!("No puzzles registered for {} (available: {})", year, years.join(", "))
+}
+
+fn get_puzzle(year: u16, day: u8) -> &'static Puzzle {
+    solutions::get_puzzle(year, day).unwrap_or_else(|| {
+        eprintln!("{} day {}: not solved yet", year, day);
+        process::exit(EXIT_USAGE);
+    })
+}
+
+/// Reads a file, or stdin for `-`.
+fn read_file(path: &str) -> Result<String, String> {
+    if path != "-" {
+        return fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e));
+    }
+
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|e| format!("stdin: {}", e))?;
+
+    Ok(input)
+}
+
+/// Reads the input named by `--input`, falling back to the cache and then to
+/// downloading (and caching) the puzzle input.
+fn read_input(options: &Options, year: u16, day: u8) -> Result<String, String> {
+    if let Some(path) = &options.input {
+        return read_file(path);
+    }
+
+    let store = InputStore::new(&options.cache_dir);
+
+    match RudolfFetcher::from_env() {
+        Some(fetcher) => store
+            .with_fetcher(fetcher)
+            .get(year, day)
+            .map_err(|e| e.to_string()),
+        None => store
+            .get_cached(year, day)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!(
+                "No input cached at {} and AOC_SESSION is not set",
+                store.path(year, day).display()
+            )),
+    }
+}
+
+/// Reads the input and solves the requested `parts` of a day, or fails with
+/// an exit code and message.
+fn solve_day(puzzle: &Puzzle, options: &Options, parts: &[u8]) -> DayResult {
+    let input = read_input(options, puzzle.year, puzzle.day)
+        .map_err(|e| (EXIT_NO_INPUT, e))?;
+
+    run_day(puzzle, &input, &options.params, parts, options.repeat)
+        .map_err(|e| (EXIT_SOLVER, e.to_string()))
+}
+
+fn exit_code(result: &DayResult) -> i32 {
+    match result {
+        Err((code, _)) => *code,
+        Ok(run) if run.parts.iter().any(|part| part.answer.is_err()) => EXIT_SOLVER,
+        Ok(_) => 0,
+    }
+}
+
+fn run(year: u16, day: u8, options: &Options) -> i32 {
+    let puzzle = get_puzzle(year, day);
+    let parts = options.parts();
+    let result = solve_day(puzzle, options, &parts);
+
+    if options.format == Format::Json {
+        for record in output::json_records(puzzle, &parts, &result) {
+            println!("{}", record);
+        }
+
+        return exit_code(&result);
+    }
+
+    let run = match &result {
+        Ok(run) => run,
+        Err((code, e)) => {
+            eprintln!("{}", e);
+            return *code;
+        },
+    };
+
+    for part in &run.parts {
+        match &part.answer {
+            Ok(answer) => println!("{}", answer),
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+
+    // answers to other inputs can't be checked against the guesses
+    if options.input.is_none() && options.params.is_empty() {
+        warn_about_guesses(year, day, run);
+    }
+
+    if options.time || options.repeat > 1 {
+        match &run.parse {
+            Some(parse) => eprintln!("parse: {}", parse.summary()),
+            None => eprintln!("parse: n/a"),
+        }
+
+        for part in &run.parts {
+            eprintln!("part {}: {}", part.part, part.time.summary());
+        }
+
+        eprintln!("total: {}", run.total().summary());
+    }
+
+    exit_code(&result)
+}
+
+/// Solves every day of `year`, printing a table of times and answers.
+fn all(year: u16, options: &Options) -> i32 {
+    let puzzles: Vec<_> = solutions::puzzles()
+        .filter(|puzzle| puzzle.year == year)
+        .collect();
+
+    if puzzles.is_empty() {
+        exit_with_usage(&unknown_year(year));
+    }
+
+    let results: Vec<_> = puzzles
+        .into_iter()
+        .map(|puzzle| {
+            let result = solve_day(puzzle, options, &[1, 2]);
+
+            if options.format == Format::Json {
+                for record in output::json_records(puzzle, &[1, 2], &result) {
+                    println!("{}", record);
+                }
+            }
+
+            (puzzle, result)
+        })
+        .collect();
+
+    if options.format == Format::Text {
+        output::print_table(&results, options.repeat);
+    }
+
+    results.iter().map(|(_, result)| exit_code(result)).max().unwrap_or(0)
+}
+
+/// Times every day with a cached input and compares the medians against the
+/// benchmark history, optionally recording them.
+fn bench(year: Option<u16>, options: &Options) -> i32 {
+    let path = history::path();
+    let history = history::load(&path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(EXIT_FAILED);
+    });
+
+    let baseline = history::baseline(&history, options.baseline.as_deref());
+
+    if let (None, Some(commit)) = (baseline, &options.baseline) {
+        eprintln!("No timings recorded for {} in {}", commit, path.display());
+        return EXIT_USAGE;
+    }
+
+    let mut current = Entry::new(options.repeat);
+
+    // never downloads, so benchmarking stays offline
+    let store = InputStore::new(&options.cache_dir);
+
+    let puzzles = solutions::puzzles()
+        .filter(|puzzle| year.is_none_or(|year| puzzle.year == year));
+
+    for puzzle in puzzles {
+        let (year, day) = (puzzle.year, puzzle.day);
+
+        let input = match store.get_cached(year, day) {
+            Ok(Some(input)) => input,
+            Ok(None) => {
+                eprintln!("Skipping {} day {}: no cached input", year, day);
+                continue;
+            },
+            Err(e) => {
+                eprintln!("Skipping {} day {}: {}", year, day, e);
+                continue;
+            },
+        };
+
+        let run = match run_day(puzzle, &input, &options.params, &[1, 2], options.repeat) {
+            Ok(run) => run,
+            Err(e) => {
+                eprintln!("Skipping {} day {}: {}", year, day, e);
+                continue;
+            },
+        };
+
+        if let Some(parse) = &run.parse {
+            current.times.insert((year, day, Step::Parse), parse.median());
+        }
+
+        for part in run.parts.iter().filter(|part| part.answer.is_ok()) {
+            current.times.insert((year, day, Step::part(part.part)), part.time.median());
+        }
+    }
+
+    let mut code = 0;
+
+    match baseline {
+        Some(baseline) => {
+            println!(
+                "comparing against {} ({} runs) with a {}% threshold",
+                baseline.label(),
+                baseline.repeat,
+                options.threshold
+            );
+            println!(
+                "{:>4} {:>3}  {:<5} {:>10} {:>10} {:>8}",
+                "year", "day", "step", "baseline", "current", "change"
+            );
+
+            for comparison in history::compare(baseline, &current, options.threshold) {
+                let (year, day, step) = comparison.key;
+                let flag = if comparison.regression { "  REGRESSION" } else { "" };
+                let change = comparison
+                    .change()
+                    .map_or("n/a".to_string(), |change| format!("{:+.1}%", change));
+
+                println!(
+                    "{:>4} {:>3}  {:<5} {:>10} {:>10} {:>8}{}",
+                    year,
+                    day,
+                    step,
+                    format_duration(comparison.baseline),
+                    format_duration(comparison.current),
+                    change,
+                    flag
+                );
+
+                if comparison.regression {
+                    code = EXIT_FAILED;
+                }
+            }
+        },
+        None => {
+            println!("no recorded timings to compare against");
+
+            for (&(year, day, step), &time) in &current.times {
+                println!("{:>4} {:>3}  {:<5} {:>10}", year, day, step, format_duration(time));
+            }
+        },
+    }
+
+    if options.save {
+        if let Err(e) = history::append(&path, &current) {
+            eprintln!("{}", e);
+            return EXIT_FAILED;
+        }
+
+        println!("recorded timings for {} in {}", current.label(), path.display());
+    }
+
+    code
+}
+
+/// Prints the progress report, or writes it into the README as Markdown.
+fn report(year: Option<u16>, options: &Options) -> i32 {
+    let answers = Answers::load(&Answers::path()).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(EXIT_FAILED);
+    });
+
+    let history = history::load(&history::path()).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(EXIT_FAILED);
+    });
+
+    let timings = history::baseline(&history, None);
+
+    let puzzles = solutions::puzzles()
+        .filter(|puzzle| year.is_none_or(|year| puzzle.year == year));
+
+    let rows = report::rows(puzzles, &answers, timings);
+
+    if !options.readme {
+        match options.format {
+            Format::Markdown => print!("{}", report::markdown(&rows, timings)),
+            _ => print!("{}", report::text(&rows, timings)),
+        }
+
+        return 0;
+    }
+
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("README.md");
+
+    let updated = fs::read_to_string(&path)
+        .map(|readme| report::update_readme(&readme, &report::markdown(&rows, timings)))
+        .and_then(|readme| fs::write(&path, readme));
+
+    match updated {
+        Ok(()) => {
+            println!("updated {}", path.display());
+            0
+        },
+        Err(e) => {
+            eprintln!("{}: {}", path.display(), e);
+            EXIT_FAILED
+        },
+    }
+}
+
+/// Warns about answers that earlier guesses at the puzzle rule out.
+fn warn_about_guesses(year: u16, day: u8, run: &DayRun) {
+    let history = match Submissions::load(&Submissions::path()) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        },
+    };
+
+    for part in &run.parts {
+        let reason = part
+            .answer
+            .as_ref()
+            .ok()
+            .and_then(|answer| history.bounds(year, day, part.part).check(answer));
+
+        if let Some(reason) = reason {
+            eprintln!("warning: part {}: {}", part.part, reason);
+        }
+    }
+}
+
+fn guesses(year: u16, day: u8) -> i32 {
+    let history = Submissions::load(&Submissions::path()).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(EXIT_FAILED);
+    });
+
+    for part in 1..=2 {
+        let submissions: Vec<_> = history.part(year, day, part).collect();
+
+        if submissions.is_empty() {
+            println!("part {}: no guesses", part);
+            continue;
+        }
+
+        match history.accepted(year, day, part) {
+            Some(accepted) => println!("part {}: solved, {}", part, accepted.answer),
+            None => println!("part {}: {}", part, history.bounds(year, day, part)),
+        }
+
+        for submission in submissions {
+            println!("    {:>16}  {}", submission.answer.to_string(), submission.verdict);
+        }
+    }
+
+    0
+}
+
+/// Solves a part and submits the answer, recording the verdict and, if it is
+/// right, the answer.
+fn submit(year: u16, day: u8, part: u8, options: &Options) -> i32 {
+    let puzzle = get_puzzle(year, day);
+
+    let answer = match solve_day(puzzle, options, &[part]) {
+        Ok(mut run) => match run.parts.remove(0).answer {
+            Ok(answer) => answer,
+            Err(e) => {
+                eprintln!("{}", e);
+                return EXIT_SOLVER;
+            },
+        },
+        Err((code, e)) => {
+            eprintln!("{}", e);
+            return code;
+        },
+    };
+
+    let path = Submissions::path();
+    let mut history = Submissions::load(&path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(EXIT_FAILED);
+    });
+
+    if let Some(accepted) = history.accepted(year, day, part) {
+        println!("{} day {} part {} was already solved: {}", year, day, part, accepted.answer);
+
+        if accepted.answer != answer {
+            eprintln!("but the solver now answers {}", answer);
+            return EXIT_FAILED;
+        }
+
+        return 0;
+    }
+
+    let Some(backend) = HttpBackend::from_env() else {
+        eprintln!("AOC_SESSION is not set");
+        return EXIT_USAGE;
+    };
+
+    let verdict = match history.submit(&path, &backend, year, day, part, answer.clone()) {
+        Ok(verdict) => verdict,
+        Err(e) => {
+            eprintln!("Not submitted: {}", e);
+            return EXIT_FAILED;
+        },
+    };
+
+    println!("{}: {}", answer, verdict);
+
+    if verdict != Verdict::Right {
+        return EXIT_FAILED;
+    }
+
+    let answers_path = Answers::path();
+    let saved = Answers::load(&answers_path).and_then(|mut answers| {
+        answers.set(year, day, part, answer);
+        answers.save(&answers_path)
+    });
+
+    if let Err(e) = saved {
+        eprintln!("Could not record the answer in {}: {}", answers_path.display(), e);
+    }
+
+    0
+}
+
+/// Generates a day from the template in this crate's source tree.
+fn new(year: u16, day: u8, options: &Options) -> i32 {
+    let example = match options.input.as_deref() {
+        Some(_) => read_input(options, year, day).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            process::exit(EXIT_NO_INPUT);
+        }),
+        None => String::new(),
+    };
+
+    let title = options.title.clone().unwrap_or_else(|| format!("Day {}", day));
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    match scaffold::new_day(root, year, day, &title, &example) {
+        Ok(changed) => {
+            for path in changed {
+                println!("wrote {}", path.strip_prefix(root).unwrap_or(&path).display());
+            }
+
+            0
+        },
+        Err(e) => {
+            eprintln!("{}", e);
+            EXIT_FAILED
+        },
+    }
+}
+
+fn list(year: Option<u16>) -> i32 {
+    if let Some(year) = year.filter(|&year| solutions::get_year(year).is_none()) {
+        exit_with_usage(&unknown_year(year));
+    }
+
+    let puzzles = solutions::puzzles()
+        .filter(|puzzle| year.is_none_or(|year| puzzle.year == year));
+
+    for puzzle in puzzles {
+        let params: Vec<_> = puzzle
+            .params()
+            .iter()
+            .map(|param| format!("{}={}", param.name, param.default))
+            .collect();
+
+        if params.is_empty() {
+            println!("{} day {:>2}: {}", puzzle.year, puzzle.day, puzzle.title);
+        } else {
+            println!(
+                "{} day {:>2}: {} ({})",
+                puzzle.year,
+                puzzle.day,
+                puzzle.title,
+                params.join(", ")
+            );
+        }
+    }
+
+    0
+}
+
+fn years() -> i32 {
+    for year in solutions::years() {
+        let days = year.puzzles.len();
+        println!("{}: {} day{}", year.year, days, if days == 1 { "" } else { "s" });
+    }
+
+    0
+}
+
+fn inputs(command: Inputs, options: &Options) -> i32 {
+    let store = InputStore::new(&options.cache_dir);
+
+    let result = match command {
+        Inputs::List { year } => store.list(year).map(|cached| {
+            if cached.is_empty() {
+                println!("no inputs cached in {}", store.dir().display());
+            }
+
+            for cached in &cached {
+                let note = match cached.integrity {
+                    Integrity::Verified => "",
+                    Integrity::Unhashed => " (no hash recorded)",
+                    Integrity::Mismatch => " CHANGED since it was cached",
+                };
+
+                println!("{} day {:>2}: {} bytes{}", cached.year, cached.day, cached.len, note);
+            }
+
+            cached.iter().any(|cached| cached.integrity == Integrity::Mismatch)
+        }),
+        Inputs::Import { year, day, path } => {
+            let input = read_file(&path).unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                process::exit(EXIT_NO_INPUT);
+            });
+
+            store.import(year, day, &input).map(|()| {
+                println!("cached {}", store.path(year, day).display());
+                false
+            })
+        },
+        Inputs::Clear { year, day } => store.clear(year, day).map(|removed| {
+            println!("removed {} cached inputs from {}", removed, store.dir().display());
+            false
+        }),
+    };
+
+    match result {
+        Ok(false) => 0,
+        Ok(true) => EXIT_FAILED,
+        Err(e) => {
+            eprintln!("{}: {}", store.dir().display(), e);
+            EXIT_FAILED
+        },
+    }
+}
+
+fn verify(dir: &Path, year: Option<u16>, day: Option<u8>) -> i32 {
+    let answers = Answers::load(&Answers::path()).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(EXIT_FAILED);
+    });
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    let puzzles = solutions::puzzles().filter(|puzzle| {
+        year.is_none_or(|year| puzzle.year == year)
+            && day.is_none_or(|day| puzzle.day == day)
+    });
+
+    let store = InputStore::new(dir);
+
+    for puzzle in puzzles {
+        let input = store.get_cached(puzzle.year, puzzle.day)
+            .unwrap_or_else(|e| {
+                eprintln!("{} day {}: {}", puzzle.year, puzzle.day, e);
+                process::exit(EXIT_NO_INPUT);
+            });
+
+        for check in solutions::verify(puzzle, input.as_deref(), &answers) {
+            match check.outcome {
+                Outcome::Pass(_) => passed += 1,
+                Outcome::Fail { .. } | Outcome::Error(_) => failed += 1,
+                Outcome::MissingAnswer(_) | Outcome::MissingInput => missing += 1,
+            }
+
+            println!("{}", check);
+        }
+    }
+
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+
+    if failed > 0 {
+        EXIT_FAILED
+    } else {
+        0
+    }
+}
+
+fn examples(year: Option<u16>, day: Option<u8>) -> i32 {
+    let examples = fixtures::load_all(&fixtures::dir()).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(EXIT_FAILED);
+    });
+
+    let (mut passed, mut failed) = (0, 0);
+
+    let examples = examples.iter().filter(|example| {
+        year.is_none_or(|year| example.year == year) && day.is_none_or(|day| example.day == day)
+    });
+
+    for example in examples {
+        for check in example.check() {
+            if check.outcome.is_regression() {
+                failed += 1;
+            } else {
+                passed += 1;
+            }
+
+            println!("{} ({})", check, example.name);
+        }
+    }
+
+    println!("{} passed, {} failed", passed, failed);
+
+    if failed > 0 {
+        EXIT_FAILED
+    } else {
+        0
+    }
+}
+
+fn watch(year: u16, day: u8, options: &Options) -> ! {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let input = InputStore::new(&options.cache_dir).path(year, day);
+    let watch = watch::Watch::new(root, year, day, &options.cache_dir, input);
+
+    println!("watching:");
+    for path in watch.paths() {
+        println!("    {}", path.strip_prefix(root).unwrap_or(path).display());
+    }
+
+    watch.watch()
+}
+
+fn main() {
+    let args = Arguments::parse(env::args().skip(1))
+        .unwrap_or_else(|e| exit_with_usage(&e));
+
+    let options = &args.options;
+    solutions::diagnostics::enable(options.verbose);
+
+    let code = match args.command {
+        Command::Run { year, day } => run(year, day, options),
+        Command::All { year } => all(year, options),
+        Command::List { year } => list(year),
+        Command::Years => years(),
+        Command::Verify { year, day } => verify(&options.cache_dir, year, day),
+        Command::Examples { year, day } => examples(year, day),
+        Command::Watch { year, day } => watch(year, day, options),
+        Command::Bench { year } => bench(year, options),
+        Command::Report { year } => report(year, options),
+        Command::Submit { year, day, part } => submit(year, day, part, options),
+        Command::Guesses { year, day } => guesses(year, day),
+        Command::New { year, day } => new(year, day, options),
+        Command::Inputs(command) => inputs(command, options),
+        Command::Help => {
+            println!("{}", USAGE);
+            0
+        },
+    };
+
+    process::exit(code);
+}
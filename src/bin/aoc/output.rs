@@ -0,0 +1,118 @@
+use solutions::json::Value;
+use solutions::Puzzle;
+
+use crate::timing::{ DayRun, Samples };
+
+/// A day's run, or the exit code and message explaining why it failed.
+pub type DayResult = Result<DayRun, (i32, String)>;
+
+fn nanos(samples: &Samples) -> Value {
+    samples.median().as_nanos().into()
+}
+
+/// An object per part, with its answer or error and the median times.
+pub fn json_records(puzzle: &Puzzle, parts: &[u8], result: &DayResult) -> Vec<Value> {
+    let record = |part: u8, answer, error, parse, elapsed| {
+        Value::Object(vec![
+            ("year".to_string(), puzzle.year.into()),
+            ("day".to_string(), puzzle.day.into()),
+            ("part".to_string(), part.into()),
+            ("answer".to_string(), answer),
+            ("error".to_string(), error),
+            ("parse_ns".to_string(), parse),
+            ("elapsed_ns".to_string(), elapsed),
+        ])
+    };
+
+    match result {
+        Ok(run) => run
+            .parts
+            .iter()
+            .map(|part| {
+                let (answer, error) = match &part.answer {
+                    Ok(answer) => (answer.to_json(), Value::Null),
+                    Err(e) => (Value::Null, e.to_string().into()),
+                };
+
+                let parse = run.parse.as_ref().map_or(Value::Null, nanos);
+                record(part.part, answer, error, parse, nanos(&part.time))
+            })
+            .collect(),
+        Err((_, e)) => parts
+            .iter()
+            .map(|&part| {
+                record(part, Value::Null, e.as_str().into(), Value::Null, Value::Null)
+            })
+            .collect(),
+    }
+}
+
+/// Prints the times and answers of each day, then any errors on stderr.
+pub fn print_table(results: &[(&Puzzle, DayResult)], repeat: usize) {
+    if repeat > 1 {
+        println!("times are min/median of {} runs", repeat);
+    }
+
+    // min/median cells need more room than single times
+    let width = if repeat > 1 { 15 } else { 8 };
+    let row = |day: &str, times: [&str; 4], answers: &str| {
+        let times: Vec<_> = times
+            .iter()
+            .map(|time| format!("{:>w$}", time, w = width))
+            .collect();
+
+        let line = format!("{:>3} {}  {}", day, times.join(" "), answers);
+        println!("{}", line.trim_end());
+    };
+
+    row("day", ["parse", "part 1", "part 2", "total"], "answers");
+
+    let mut errors = Vec::new();
+    let mut total = Samples::default();
+
+    for (puzzle, result) in results {
+        let day = puzzle.day.to_string();
+
+        let run = match result {
+            Ok(run) => run,
+            Err((_, e)) => {
+                row(&day, ["-", "-", "-", "-"], "");
+                errors.push(format!("{} day {}: {}", puzzle.year, puzzle.day, e));
+                continue;
+            },
+        };
+
+        let mut answers = Vec::new();
+        let mut times = Vec::new();
+
+        for part in &run.parts {
+            match &part.answer {
+                Ok(answer) => {
+                    answers.push(answer.to_string());
+                    times.push(part.time.summary());
+                },
+                Err(e) => {
+                    answers.push("-".to_string());
+                    times.push("error".to_string());
+                    errors.push(e.to_string());
+                },
+            }
+        }
+
+        let parse = run.parse.as_ref().map_or("n/a".to_string(), Samples::summary);
+        let day_total = run.total();
+        total = &total + &day_total;
+
+        row(
+            &day,
+            [&parse, &times[0], &times[1], &day_total.summary()],
+            &answers.join(", "),
+        );
+    }
+
+    row("all", ["", "", "", &total.summary()], "");
+
+    for error in errors {
+        eprintln!("{}", error);
+    }
+}
@@ -0,0 +1,215 @@
+//! A progress report of the registered days: the stars earned, per the
+//! answers file, and how long each part took in the latest benchmark run.
+
+use std::time::Duration;
+
+use solutions::{ Answers, Puzzle };
+
+use crate::history::{ Entry, Step };
+use crate::scaffold::year_module;
+use crate::timing::format_duration;
+
+/// Marks the part of the README holding the report.
+const BEGIN: &str = "<!-- progress -->";
+const END: &str = "<!-- /progress -->";
+
+pub struct Row {
+    pub year: u16,
+    pub day: u8,
+    pub title: &'static str,
+    pub stars: usize,
+    pub times: [Option<Duration>; 2],
+    /// the source module, relative to the crate root
+    pub source: String,
+}
+
+pub fn rows<'a>(
+    puzzles: impl Iterator<Item = &'a Puzzle>,
+    answers: &Answers,
+    timings: Option<&Entry>,
+) -> Vec<Row> {
+    puzzles
+        .map(|puzzle| {
+            let Puzzle { year, day, title, .. } = *puzzle;
+
+            let time = |step| timings.and_then(|entry| entry.times.get(&(year, day, step)).copied());
+
+            Row {
+                year,
+                day,
+                title,
+                stars: (1..=2).filter(|&part| answers.get(year, day, part).is_some()).count(),
+                times: [time(Step::Part1), time(Step::Part2)],
+                source: format!(
+                    "src/{}/day{:02}.rs",
+                    year_module(year).unwrap_or_default(),
+                    day
+                ),
+            }
+        })
+        .collect()
+}
+
+fn stars(stars: usize) -> String {
+    "★".repeat(stars)
+}
+
+fn time(time: Option<Duration>) -> String {
+    time.map_or("-".to_string(), format_duration)
+}
+
+// the rows of each year, in order
+fn years(rows: &[Row]) -> Vec<(u16, &[Row])> {
+    rows.chunk_by(|a, b| a.year == b.year)
+        .map(|rows| (rows[0].year, rows))
+        .collect()
+}
+
+fn total_stars(rows: &[Row]) -> usize {
+    rows.iter().map(|row| row.stars).sum()
+}
+
+fn timings_note(timings: Option<&Entry>) -> String {
+    match timings {
+        Some(entry) => format!("Times are medians of {} runs at {}.", entry.repeat, entry.label()),
+        None => "No benchmark timings recorded.".to_string(),
+    }
+}
+
+pub fn markdown(rows: &[Row], timings: Option<&Entry>) -> String {
+    let mut report = String::new();
+
+    for (year, rows) in years(rows) {
+        report += &format!("### {} ({} stars)\n\n", year, total_stars(rows));
+        report += "| Day | Title | Stars | Part 1 | Part 2 | Source |\n";
+        report += "| ---: | --- | :---: | ---: | ---: | --- |\n";
+
+        for row in rows {
+            let file = row.source.rsplit('/').next().unwrap_or(&row.source);
+
+            report += &format!(
+                "| {} | {} | {} | {} | {} | [{}]({}) |\n",
+                row.day,
+                row.title.replace('|', "\\|"),
+                stars(row.stars),
+                time(row.times[0]),
+                time(row.times[1]),
+                file,
+                row.source
+            );
+        }
+
+        report += "\n";
+    }
+
+    report + &timings_note(timings) + "\n"
+}
+
+pub fn text(rows: &[Row], timings: Option<&Entry>) -> String {
+    let mut report = String::new();
+
+    for (year, rows) in years(rows) {
+        let width = rows.iter().map(|row| row.title.chars().count()).max().unwrap_or(0);
+
+        report += &format!("{}: {} stars\n", year, total_stars(rows));
+        report += &format!(
+            "{:>3}  {:<w$}  {:<5}  {:>8}  {:>8}  source\n",
+            "day", "title", "stars", "part 1", "part 2", w = width
+        );
+
+        for row in rows {
+            report += &format!(
+                "{:>3}  {:<w$}  {:<5}  {:>8}  {:>8}  {}\n",
+                row.day,
+                row.title,
+                "*".repeat(row.stars),
+                time(row.times[0]),
+                time(row.times[1]),
+                row.source,
+                w = width
+            );
+        }
+
+        report += "\n";
+    }
+
+    report + &timings_note(timings) + "\n"
+}
+
+/// Replaces the report in a README, or appends it if there isn't one.
+pub fn update_readme(readme: &str, report: &str) -> String {
+    let section = format!("{}\n{}{}\n", BEGIN, report, END);
+
+    match (readme.find(BEGIN), readme.find(END)) {
+        (Some(begin), Some(end)) if begin < end => {
+            let rest = readme[end + END.len()..].trim_start_matches('\n');
+            let gap = if rest.is_empty() { "" } else { "\n" };
+
+            format!("{}{}{}{}", &readme[..begin], section, gap, rest)
+        },
+        _ => format!("{}\n\n{}", readme.trim_end_matches('\n'), section),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{markdown, text, update_readme, Row};
+
+    fn rows() -> Vec<Row> {
+        vec![
+            Row {
+                year: 2024,
+                day: 1,
+                title: "Historian Hysteria",
+                stars: 2,
+                times: [Some(Duration::from_micros(51)), None],
+                source: "src/twenty_four/day01.rs".to_string(),
+            },
+            Row {
+                year: 2024,
+                day: 2,
+                title: "Red-Nosed Reports",
+                stars: 1,
+                times: [None, None],
+                source: "src/twenty_four/day02.rs".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_markdown() {
+        assert_eq!(markdown(&rows(), None), "\
+### 2024 (3 stars)
+
+| Day | Title | Stars | Part 1 | Part 2 | Source |
+| ---: | --- | :---: | ---: | ---: | --- |
+| 1 | Historian Hysteria | ★★ | 51.0µs | - | [day01.rs](src/twenty_four/day01.rs) |
+| 2 | Red-Nosed Reports | ★ | - | - | [day02.rs](src/twenty_four/day02.rs) |
+
+No benchmark timings recorded.
+");
+    }
+
+    #[test]
+    fn test_text() {
+        let report = text(&rows(), None);
+        let lines: Vec<_> = report.lines().collect();
+
+        assert_eq!(lines[0], "2024: 3 stars");
+        assert_eq!(
+            lines[2],
+            "  1  Historian Hysteria  **       51.0µs         -  src/twenty_four/day01.rs"
+        );
+    }
+
+    #[test]
+    fn test_update_readme() {
+        let readme = update_readme("# aoc2024\n", "one\n");
+        assert_eq!(readme, "# aoc2024\n\n<!-- progress -->\none\n<!-- /progress -->\n");
+
+        let readme = update_readme(&(readme + "\nmore\n"), "two\n");
+        assert_eq!(readme, "# aoc2024\n\n<!-- progress -->\ntwo\n<!-- /progress -->\n\nmore\n");
+    }
+}
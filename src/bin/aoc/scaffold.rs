@@ -0,0 +1,258 @@
+//! Generates the module for a new day from the solution template and
+//! registers it, creating the year's module if needed.
+
+use std::fs;
+use std::path::{ Path, PathBuf };
+
+use solutions::fixtures;
+
+const TEMPLATE: &str = include_str!("../../twenty_four/day00.rs");
+
+const ONES: [&str; 10] = [
+    "", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+const TEENS: [&str; 10] = [
+    "ten", "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen",
+    "seventeen", "eighteen", "nineteen",
+];
+
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty",
+    "ninety",
+];
+
+/// The module holding a year's days, e.g. `twenty_four` for 2024.
+pub fn year_module(year: u16) -> Option<String> {
+    if !(2015..=2099).contains(&year) {
+        return None;
+    }
+
+    let n = (year % 100) as usize;
+
+    let name = match (n / 10, n % 10) {
+        (1, ones) => TEENS[ones].to_string(),
+        (tens, 0) => TENS[tens].to_string(),
+        (tens, ones) => format!("{}_{}", TENS[tens], ONES[ones]),
+    };
+
+    Some(name)
+}
+
+fn module_year(module: &str) -> Option<u16> {
+    (2015..=2099).find(|&year| year_module(year).as_deref() == Some(module))
+}
+
+// an example without answers yet, which the fixture harness skips
+const EXAMPLES: &str = "[\n  {\"input\": \"example.txt\", \"part1\": null, \"part2\": null}\n]\n";
+
+/// Adds a day to the `puzzles!` table of a year module, keeping it sorted.
+fn register_day(source: &str, day: u8, title: &str) -> Result<String, String> {
+    let entry_day = |line: &str| line
+        .trim()
+        .split_once(" => ")
+        .and_then(|(day, _)| day.parse::<u8>().ok());
+
+    let lines: Vec<_> = source.lines().collect();
+
+    if lines.iter().any(|line| entry_day(line) == Some(day)) {
+        return Err(format!("Day {} is already registered", day));
+    }
+
+    let year_line = lines
+        .iter()
+        .position(|line| line.trim().trim_end_matches(';').parse::<u16>().is_ok())
+        .ok_or("No puzzles! table found")?;
+
+    // after the last entry before this day, or after the year
+    let at = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| entry_day(line).is_some_and(|d| d < day))
+        .map(|(i, _)| i + 1)
+        .next_back()
+        .unwrap_or(year_line + 1);
+
+    let entry = format!(
+        "    {} => day{:02}, \"{}\";",
+        day,
+        day,
+        title.replace('\\', "\\\\").replace('"', "\\\"")
+    );
+
+    let mut lines: Vec<_> = lines.iter().map(|line| line.to_string()).collect();
+    lines.insert(at, entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds a year module to the `years!` table in the crate root, keeping it
+/// in year order.
+fn register_year(source: &str, module: &str) -> Result<String, String> {
+    let entry = format!("    {},", module);
+    let mut lines: Vec<_> = source.lines().map(|line| line.to_string()).collect();
+
+    if lines.contains(&entry) {
+        return Ok(source.to_string());
+    }
+
+    let table = lines
+        .iter()
+        .position(|line| line.starts_with("years! {"))
+        .ok_or("No years! table found")?;
+
+    let table_end = table + lines[table..]
+        .iter()
+        .position(|line| line == "}")
+        .ok_or("Unterminated years! table")?;
+
+    let year = module_year(module);
+
+    let at = (table + 1..table_end)
+        .find(|&i| module_year(lines[i].trim().trim_end_matches(',')) > year)
+        .unwrap_or(table_end);
+
+    lines.insert(at, entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Creates the module and example fixture for a day under `root`, the crate
+/// root, returning the files created or changed.
+pub fn new_day(
+    root: &Path,
+    year: u16,
+    day: u8,
+    title: &str,
+    example: &str,
+) -> Result<Vec<PathBuf>, String> {
+    let module = year_module(year).ok_or_else(|| format!("Unsupported year {}", year))?;
+    let year_dir = root.join("src").join(&module);
+    let day_path = year_dir.join(format!("day{:02}.rs", day));
+    let mod_path = year_dir.join("mod.rs");
+    let lib_path = root.join("src").join("lib.rs");
+    let examples_dir = fixtures::day_dir(&root.join("fixtures"), year, day);
+    let example_path = examples_dir.join("example.txt");
+    let examples_path = examples_dir.join("examples.json");
+
+    let read = |path: &Path| fs::read_to_string(path)
+        .map_err(|e| format!("{}: {}", path.display(), e));
+
+    let write = |path: &Path, contents: &str| {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("{}: {}", parent.display(), e))?;
+        }
+
+        fs::write(path, contents).map_err(|e| format!("{}: {}", path.display(), e))
+    };
+
+    if day_path.exists() {
+        return Err(format!("{} already exists", day_path.display()));
+    }
+
+    let mut changed = Vec::new();
+
+    // check everything can be registered before writing anything
+    let (year_source, lib_source) = if mod_path.exists() {
+        (read(&mod_path)?, None)
+    } else {
+        let lib = register_year(&read(&lib_path)?, &module)?;
+        (format!("puzzles! {{\n    {};\n}}\n", year), Some(lib))
+    };
+
+    let year_source = register_day(&year_source, day, title)?;
+
+    if let Some(lib_source) = lib_source {
+        write(&lib_path, &lib_source)?;
+        changed.push(lib_path);
+    }
+
+    write(&mod_path, &year_source)?;
+    changed.push(mod_path);
+
+    write(&day_path, TEMPLATE.trim_start_matches("// solution template\n\n"))?;
+    changed.push(day_path);
+
+    for (path, contents) in [(example_path, example), (examples_path, EXAMPLES)] {
+        if !path.exists() {
+            write(&path, contents)?;
+            changed.push(path);
+        }
+    }
+
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use solutions::temp::TempDir;
+
+    use super::{new_day, register_day, register_year, year_module};
+
+    const LIB: &str = "\
+mod util;
+
+years! {
+    twenty_four,
+}
+";
+
+    #[test]
+    fn test_year_module() {
+        assert_eq!(year_module(2015).as_deref(), Some("fifteen"));
+        assert_eq!(year_module(2020).as_deref(), Some("twenty"));
+        assert_eq!(year_module(2024).as_deref(), Some("twenty_four"));
+        assert_eq!(year_module(2014), None);
+    }
+
+    #[test]
+    fn test_register_day() {
+        let source = "puzzles! {\n    2024;\n    1 => day01, \"A\";\n    7 => day07, \"B\";\n}\n";
+
+        assert_eq!(
+            register_day(source, 6, "Guard \"Gallivant\"").unwrap(),
+            "puzzles! {\n    2024;\n    1 => day01, \"A\";\n    6 => day06, \"Guard \\\"Gallivant\\\"\";\n    7 => day07, \"B\";\n}\n"
+        );
+        assert_eq!(
+            register_day("puzzles! {\n    2025;\n}\n", 1, "C").unwrap(),
+            "puzzles! {\n    2025;\n    1 => day01, \"C\";\n}\n"
+        );
+        assert!(register_day(source, 7, "B").is_err());
+    }
+
+    #[test]
+    fn test_register_year() {
+        let lib = register_year(LIB, "fifteen").unwrap();
+
+        assert_eq!(lib, "\
+mod util;
+
+years! {
+    fifteen,
+    twenty_four,
+}
+");
+        assert_eq!(register_year(&lib, "fifteen").unwrap(), lib);
+        assert!(register_year(&lib, "twenty_five").unwrap().ends_with("    twenty_four,\n    twenty_five,\n}\n"));
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = TempDir::new("scaffold");
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/lib.rs"), LIB).unwrap();
+
+        let changed = new_day(&root, 2025, 3, "Lobby", "123\n").unwrap();
+
+        assert_eq!(changed.len(), 5);
+        assert!(fs::read_to_string(root.join("src/lib.rs")).unwrap().contains("    twenty_five,"));
+        assert!(fs::read_to_string(root.join("src/twenty_five/day03.rs"))
+            .unwrap()
+            .starts_with("use crate::"));
+        assert_eq!(fs::read_to_string(root.join("fixtures/2025/day03/example.txt")).unwrap(), "123\n");
+        assert!(solutions::fixtures::load(&root.join("fixtures"), 2025, 3).is_ok());
+        assert!(new_day(&root, 2025, 3, "Lobby", "").is_err());
+    }
+}
@@ -0,0 +1,158 @@
+use std::ops::Add;
+use std::time::{ Duration, Instant };
+
+use solutions::{ Answer, Params, Puzzle, SolveError };
+
+/// Wall times of repeated runs of the same step.
+#[derive(Clone, Default, Debug)]
+pub struct Samples {
+    times: Vec<Duration>,
+}
+
+impl Samples {
+    pub fn push(&mut self, time: Duration) {
+        self.times.push(time);
+    }
+
+    pub fn min(&self) -> Duration {
+        self.times.iter().copied().min().unwrap_or_default()
+    }
+
+    pub fn median(&self) -> Duration {
+        let mut times = self.times.clone();
+        times.sort();
+        times.get(times.len() / 2).copied().unwrap_or_default()
+    }
+
+    /// The min and median, or just the time if there was a single run.
+    pub fn summary(&self) -> String {
+        if self.times.len() > 1 {
+            let (min, median) = (self.min(), self.median());
+            format!("{}/{}", format_duration(min), format_duration(median))
+        } else {
+            format_duration(self.median())
+        }
+    }
+}
+
+impl Add for &Samples {
+    type Output = Samples;
+
+    /// Adds the runs pairwise, so the sum of steps is timed per run.
+    fn add(self, other: &Samples) -> Samples {
+        if self.times.is_empty() {
+            return other.clone();
+        }
+
+        let times = self
+            .times
+            .iter()
+            .zip(&other.times)
+            .map(|(a, b)| *a + *b)
+            .collect();
+
+        Samples { times }
+    }
+}
+
+pub struct PartRun {
+    pub part: u8,
+    pub answer: Result<Answer, SolveError>,
+    pub time: Samples,
+}
+
+/// The answers to a day, and how long parsing and each part took. Days whose
+/// parts parse the input themselves have no parse time of their own.
+pub struct DayRun {
+    pub parse: Option<Samples>,
+    pub parts: Vec<PartRun>,
+}
+
+impl DayRun {
+    pub fn total(&self) -> Samples {
+        let parse = self.parse.clone().unwrap_or_default();
+        self.parts.iter().fold(parse, |total, part| &total + &part.time)
+    }
+}
+
+/// Solves the requested `parts` `repeat` times, keeping the answers of the
+/// first run, or fails if the input could not be parsed.
+pub fn run_day(
+    puzzle: &Puzzle,
+    input: &str,
+    params: &Params,
+    parts: &[u8],
+    repeat: usize,
+) -> Result<DayRun, SolveError> {
+    let parse = puzzle.solver.parses_input().then(Samples::default);
+    let mut run = DayRun { parse, parts: Vec::new() };
+
+    for i in 0..repeat.max(1) {
+        let start = Instant::now();
+        let prepared = puzzle
+            .solver
+            .prepare(input, params)
+            .map_err(|e| e.in_day(puzzle.day))?;
+
+        if let Some(parse) = &mut run.parse {
+            parse.push(start.elapsed());
+        }
+
+        for (j, &part) in parts.iter().enumerate() {
+            let start = Instant::now();
+            let answer = match part {
+                1 => prepared.part1(),
+                _ => prepared.part2(),
+            };
+            let time = start.elapsed();
+
+            if i == 0 {
+                let answer = answer.map_err(|e| e.in_puzzle(puzzle.day, part));
+                run.parts.push(PartRun { part, answer, time: Samples::default() });
+            }
+
+            run.parts[j].time.push(time);
+        }
+    }
+
+    Ok(run)
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+
+    match nanos {
+        n if n < 1e3 => format!("{}ns", n),
+        n if n < 1e6 => format!("{:.1}µs", n / 1e3),
+        n if n < 1e9 => format!("{:.1}ms", n / 1e6),
+        n => format!("{:.2}s", n / 1e9),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_duration, Samples};
+
+    #[test]
+    fn test_samples() {
+        let mut samples = Samples::default();
+
+        for ms in [5, 1, 3, 4] {
+            samples.push(Duration::from_millis(ms));
+        }
+
+        assert_eq!(samples.min(), Duration::from_millis(1));
+        assert_eq!(samples.median(), Duration::from_millis(4));
+        assert_eq!(samples.summary(), "1.0ms/4.0ms");
+        assert_eq!((&samples + &samples).min(), Duration::from_millis(2));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999ns");
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.5ms");
+        assert_eq!(format_duration(Duration::from_secs(2)), "2.00s");
+    }
+}
@@ -0,0 +1,283 @@
+//! Rebuilds and reruns a day whenever its source, examples or input change.
+//!
+//! The running binary can't reload itself, so each run builds the crate with
+//! cargo and runs the fresh binary's `examples` and `run` commands.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{ Path, PathBuf };
+use std::process::Command;
+use std::thread;
+use std::time::{ Duration, SystemTime };
+
+use solutions::json::Value;
+use solutions::{ fixtures, Answer, Answers };
+
+use crate::scaffold::year_module;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// The modification times of `paths`, and of the files in those that are
+/// directories. Missing paths are left out, so creating one is a change.
+fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    let mut times = Snapshot::new();
+
+    for path in paths {
+        let files: Vec<_> = match fs::read_dir(path) {
+            Ok(entries) => entries.filter_map(|entry| Some(entry.ok()?.path())).collect(),
+            Err(_) => vec![path.clone()],
+        };
+
+        for file in files {
+            if let Ok(time) = fs::metadata(&file).and_then(|metadata| metadata.modified()) {
+                times.insert(file, time);
+            }
+        }
+    }
+
+    times
+}
+
+/// The paths added, removed or modified between two snapshots.
+fn changes(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<_> = after
+        .iter()
+        .filter(|(path, time)| before.get(*path) != Some(time))
+        .map(|(path, _)| path.clone())
+        .collect();
+
+    changed.extend(before.keys().filter(|path| !after.contains_key(*path)).cloned());
+    changed
+}
+
+/// The answer or error for each part in the output of `run --format json`.
+fn parse_answers(output: &str) -> BTreeMap<u8, Result<Answer, String>> {
+    output
+        .lines()
+        .filter_map(|line| line.parse::<Value>().ok())
+        .filter_map(|record| {
+            let part = record.get("part")?.as_i64()?.try_into().ok()?;
+
+            let answer = match record.get("error").and_then(Value::as_str) {
+                Some(e) => Err(e.to_string()),
+                None => record
+                    .get("answer")
+                    .and_then(Answer::from_json)
+                    .ok_or("no answer".to_string()),
+            };
+
+            Some((part, answer))
+        })
+        .collect()
+}
+
+/// Describes a part's answer next to the previous run's and the known one.
+fn describe(
+    part: u8,
+    current: &Result<Answer, String>,
+    previous: Option<&Result<Answer, String>>,
+    known: Option<&Answer>,
+) -> String {
+    let answer = match current {
+        Ok(answer) => answer,
+        Err(e) => return format!("part {}: ERROR {}", part, e),
+    };
+
+    let change = match previous {
+        Some(previous) if previous.as_ref() == Ok(answer) => "unchanged".to_string(),
+        Some(Ok(previous)) => format!("was {}", previous),
+        Some(Err(_)) => "was an error".to_string(),
+        None => "first run".to_string(),
+    };
+
+    let check = match known {
+        Some(known) if known == answer => ", right".to_string(),
+        Some(known) => format!(", WRONG, expected {}", known),
+        None => String::new(),
+    };
+
+    format!("part {}: {} ({}{})", part, answer, change, check)
+}
+
+pub struct Watch {
+    root: PathBuf,
+    year: u16,
+    day: u8,
+    cache_dir: PathBuf,
+    paths: Vec<PathBuf>,
+}
+
+impl Watch {
+    /// Watches a day in the crate at `root`, with its input cached in
+    /// `cache_dir`.
+    pub fn new(root: &Path, year: u16, day: u8, cache_dir: &Path, input: PathBuf) -> Self {
+        let module = year_module(year).unwrap_or_default();
+
+        let paths = vec![
+            root.join("src").join(module).join(format!("day{:02}.rs", day)),
+            fixtures::day_dir(&root.join("fixtures"), year, day),
+            input,
+        ];
+
+        Watch { root: root.to_path_buf(), year, day, cache_dir: cache_dir.to_path_buf(), paths }
+    }
+
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    // runs a command of the freshly built binary, without network access
+    fn aoc(&self, args: &[&str]) -> Option<String> {
+        let output = Command::new("cargo")
+            .args(["run", "--quiet", "--release", "--bin", "aoc", "--"])
+            .args(args)
+            .arg("--cache-dir")
+            .arg(&self.cache_dir)
+            .current_dir(&self.root)
+            .env_remove("AOC_SESSION")
+            .output();
+
+        match output {
+            Ok(output) => {
+                eprint!("{}", String::from_utf8_lossy(&output.stderr));
+                Some(String::from_utf8_lossy(&output.stdout).into_owned())
+            },
+            Err(e) => {
+                eprintln!("Could not run cargo: {}", e);
+                None
+            },
+        }
+    }
+
+    /// Builds and runs the day, returning the answers for the next run to
+    /// compare against.
+    fn run(
+        &self,
+        previous: &BTreeMap<u8, Result<Answer, String>>,
+    ) -> BTreeMap<u8, Result<Answer, String>> {
+        let (year, day) = (self.year.to_string(), self.day.to_string());
+
+        let built = Command::new("cargo")
+            .args(["build", "--quiet", "--release", "--bin", "aoc"])
+            .current_dir(&self.root)
+            .status()
+            .is_ok_and(|status| status.success());
+
+        if !built {
+            println!("build failed");
+            return previous.clone();
+        }
+
+        println!("examples:");
+
+        if let Some(output) = self.aoc(&["examples", &year, &day]) {
+            print!("{}", output);
+        }
+
+        println!("input:");
+
+        let Some(output) = self.aoc(&["run", &year, &day, "--format", "json"]) else {
+            return previous.clone();
+        };
+
+        let answers = parse_answers(&output);
+        let known = Answers::load(&Answers::path()).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            Answers::new()
+        });
+
+        for (&part, answer) in &answers {
+            let known = known.get(self.year, self.day, part);
+            println!("{}", describe(part, answer, previous.get(&part), known));
+        }
+
+        answers
+    }
+
+    /// Runs the day, then again after every change, until interrupted.
+    pub fn watch(&self) -> ! {
+        let mut times = snapshot(&self.paths);
+        let mut answers = self.run(&BTreeMap::new());
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            let now = snapshot(&self.paths);
+            let changed = changes(&times, &now);
+
+            if changed.is_empty() {
+                continue;
+            }
+
+            times = now;
+
+            println!();
+            for path in changed {
+                println!("changed: {}", path.strip_prefix(&self.root).unwrap_or(&path).display());
+            }
+
+            answers = self.run(&answers);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::time::{ Duration, SystemTime };
+
+    use solutions::temp::TempDir;
+    use solutions::Answer;
+
+    use super::{changes, describe, parse_answers, snapshot};
+
+    #[test]
+    fn test_changes() {
+        let dir = TempDir::new("watch");
+        fs::write(dir.join("a.txt"), "a").unwrap();
+
+        let paths = [dir.to_path_buf(), dir.join("missing.rs")];
+        let before = snapshot(&paths);
+        assert_eq!(before.len(), 1);
+
+        fs::write(dir.join("b.txt"), "b").unwrap();
+        fs::File::options()
+            .write(true)
+            .open(dir.join("a.txt"))
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(10))
+            .unwrap();
+
+        let mut changed = changes(&before, &snapshot(&paths));
+        changed.sort();
+        assert_eq!(changed, [dir.join("a.txt"), dir.join("b.txt")]);
+
+        fs::remove_file(dir.join("a.txt")).unwrap();
+        fs::remove_file(dir.join("b.txt")).unwrap();
+        assert_eq!(changes(&before, &snapshot(&paths)), [dir.join("a.txt")]);
+    }
+
+    #[test]
+    fn test_describe() {
+        let output = concat!(
+            r#"{"year":2024,"day":1,"part":1,"answer":11,"error":null}"#, "\n",
+            r#"{"year":2024,"day":1,"part":2,"answer":null,"error":"oops"}"#, "\n",
+        );
+        let answers = parse_answers(output);
+        let previous = BTreeMap::from([(1, Ok(Answer::from(12u8)))]);
+
+        assert_eq!(
+            describe(1, &answers[&1], previous.get(&1), Some(&Answer::from(11u8))),
+            "part 1: 11 (was 12, right)"
+        );
+        assert_eq!(describe(1, &answers[&1], answers.get(&1), None), "part 1: 11 (unchanged)");
+        assert_eq!(
+            describe(1, &answers[&1], None, Some(&Answer::from(10u8))),
+            "part 1: 11 (first run, WRONG, expected 10)"
+        );
+        assert_eq!(describe(2, &answers[&2], None, None), "part 2: ERROR oops");
+    }
+}
@@ -0,0 +1,25 @@
+//! Output from solvers that is not an answer, such as a picture of the
+//! puzzle state. It goes to stderr, and only when enabled, so it never mixes
+//! with answers on stdout or slows down benchmarks and tests.
+
+use std::sync::atomic::{ AtomicBool, Ordering };
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn enable(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Like `eprintln!`, but only when diagnostics are enabled. The arguments
+/// are not evaluated otherwise.
+macro_rules! diagnostic {
+    ($($arg:tt)*) => {
+        if $crate::diagnostics::enabled() {
+            eprintln!($($arg)*);
+        }
+    };
+}
@@ -0,0 +1,174 @@
+use std::error::Error;
+use std::fmt::{ self, Display };
+use std::num::ParseIntError;
+
+/// An error raised while solving a puzzle.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum SolveError {
+    /// The input is malformed at a known (1-based) line and column.
+    Parse { line: usize, column: usize, message: String },
+    /// The input is malformed at an unknown location.
+    Input(String),
+    /// The input is well-formed, but has no answer.
+    NoAnswer(String),
+    /// An error annotated with the puzzle day and, unless it was raised
+    /// while parsing, the part it was raised in.
+    Puzzle { day: u8, part: Option<u8>, error: Box<SolveError> },
+}
+
+impl SolveError {
+    /// Reports a parse error at `token`, which must be a slice of `input`
+    /// for its line and column to be recovered.
+    pub fn at(input: &str, token: &str, message: impl Display) -> Self {
+        let start = input.as_ptr() as usize;
+        let pos = token.as_ptr() as usize;
+
+        if pos < start || pos > start + input.len() {
+            return Self::Input(message.to_string());
+        }
+
+        let before = &input[..pos - start];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        Self::Parse {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn no_answer(message: impl Display) -> Self {
+        Self::NoAnswer(message.to_string())
+    }
+
+    /// Annotates the error with the day it was raised in.
+    pub fn in_day(self, day: u8) -> Self {
+        match self {
+            Self::Puzzle { part, error, .. } => Self::Puzzle { day, part, error },
+            error => Self::Puzzle { day, part: None, error: Box::new(error) },
+        }
+    }
+
+    /// Annotates the error with the day and part it was raised in.
+    pub fn in_puzzle(self, day: u8, part: u8) -> Self {
+        let error = match self {
+            Self::Puzzle { error, .. } => error,
+            error => Box::new(error),
+        };
+
+        Self::Puzzle { day, part: Some(part), error }
+    }
+
+    pub fn day(&self) -> Option<u8> {
+        match self {
+            Self::Puzzle { day, .. } => Some(*day),
+            _ => None,
+        }
+    }
+
+    pub fn part(&self) -> Option<u8> {
+        match self {
+            Self::Puzzle { part, .. } => *part,
+            _ => None,
+        }
+    }
+
+    /// The line and column of the offending input, if known.
+    pub fn location(&self) -> Option<(usize, usize)> {
+        match self {
+            Self::Parse { line, column, .. } => Some((*line, *column)),
+            Self::Puzzle { error, .. } => error.location(),
+            _ => None,
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Self::Parse { message, .. } => message,
+            Self::Input(message) => message,
+            Self::NoAnswer(message) => message,
+            Self::Puzzle { error, .. } => error.message(),
+        }
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse { line, column, message } => {
+                write!(f, "line {}, column {}: {}", line, column, message)
+            },
+            Self::Input(message) => write!(f, "invalid input: {}", message),
+            Self::NoAnswer(message) => write!(f, "no answer: {}", message),
+            Self::Puzzle { day, part: Some(part), error } => {
+                write!(f, "day {} part {}: {}", day, part, error)
+            },
+            Self::Puzzle { day, part: None, error } => {
+                write!(f, "day {}: {}", day, error)
+            },
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+impl From<&str> for SolveError {
+    fn from(message: &str) -> Self {
+        Self::Input(message.to_string())
+    }
+}
+
+impl From<String> for SolveError {
+    fn from(message: String) -> Self {
+        Self::Input(message)
+    }
+}
+
+impl From<ParseIntError> for SolveError {
+    fn from(error: ParseIntError) -> Self {
+        Self::Input(error.to_string())
+    }
+}
+
+impl From<Box<dyn Error>> for SolveError {
+    fn from(error: Box<dyn Error>) -> Self {
+        Self::Input(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SolveError;
+
+    #[test]
+    fn test_at() {
+        let input = "1 2\n3 x 5\n";
+        let token = &input[6..7];
+
+        assert_eq!(
+            SolveError::at(input, token, "not a number"),
+            SolveError::Parse {
+                line: 2,
+                column: 3,
+                message: "not a number".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_in_puzzle() {
+        let error = SolveError::no_answer("no path").in_puzzle(16, 1);
+
+        assert_eq!(error.day(), Some(16));
+        assert_eq!(error.part(), Some(1));
+        assert_eq!(error.to_string(), "day 16 part 1: no answer: no path");
+    }
+
+    #[test]
+    fn test_in_day() {
+        let error = SolveError::from("Start not found").in_day(16);
+
+        assert_eq!(error.part(), None);
+        assert_eq!(error.to_string(), "day 16: invalid input: Start not found");
+    }
+}
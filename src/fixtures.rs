@@ -0,0 +1,218 @@
+//! Example inputs from the puzzle descriptions, with their expected answers.
+//!
+//! Each day's examples live in `fixtures/<year>/day<NN>/`, listed in an
+//! `examples.json` giving the input file, any parameters to override and the
+//! expected answer to either part:
+//!
+//! ```text
+//! [
+//!   {"input": "example.txt", "params": {"size": 6, "bytes": 12}, "part1": 22},
+//!   {"input": "example.txt", "params": {"size": 6}, "part2": [6,1]}
+//! ]
+//! ```
+//!
+//! A part without an answer, or with `null`, is not checked.
+
+use std::fs;
+use std::path::{ Path, PathBuf };
+
+use crate::json::Value;
+use crate::{ get_puzzle, Answer, Check, Outcome, Params, SolveError };
+
+/// The fixtures directory of this crate.
+pub fn dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures")
+}
+
+/// Where the examples of a day live under `dir`.
+pub fn day_dir(dir: &Path, year: u16, day: u8) -> PathBuf {
+    dir.join(year.to_string()).join(format!("day{:02}", day))
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Example {
+    pub year: u16,
+    pub day: u8,
+    /// the input's file name
+    pub name: String,
+    pub input: String,
+    pub params: Params,
+    /// the expected answers to part 1 and part 2, if known
+    pub answers: [Option<Answer>; 2],
+}
+
+impl Example {
+    fn from_json(year: u16, day: u8, value: &Value, dir: &Path) -> Result<Self, String> {
+        let name = value
+            .get("input")
+            .and_then(Value::as_str)
+            .ok_or("Missing or invalid field 'input'")?;
+
+        let path = dir.join(name);
+        let input = fs::read_to_string(&path)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        let mut params = Params::new();
+
+        match value.get("params") {
+            Some(Value::Object(values)) => {
+                for (param, value) in values {
+                    let value = value
+                        .as_i64()
+                        .ok_or_else(|| format!("Invalid value for param '{}'", param))?;
+
+                    params.set(param, value);
+                }
+            },
+            None | Some(Value::Null) => (),
+            Some(_) => return Err("Field 'params' is not an object".to_string()),
+        }
+
+        let answer = |part: &str| match value.get(part) {
+            None | Some(Value::Null) => Ok(None),
+            Some(answer) => Answer::from_json(answer)
+                .map(Some)
+                .ok_or_else(|| format!("Invalid answer to {}", part)),
+        };
+
+        Ok(Example {
+            year,
+            day,
+            name: name.to_string(),
+            input,
+            params,
+            answers: [answer("part1")?, answer("part2")?],
+        })
+    }
+
+    /// Solves the example and checks the parts with an expected answer.
+    pub fn check(&self) -> Vec<Check> {
+        let Example { year, day, .. } = *self;
+        let check = |part, outcome| Check { year, day, part, outcome };
+
+        let expected: Vec<_> = (1..=2)
+            .zip(&self.answers)
+            .filter_map(|(part, answer)| Some((part, answer.clone()?)))
+            .collect();
+
+        let prepared = get_puzzle(year, day)
+            .ok_or_else(|| SolveError::from("No solution registered"))
+            .and_then(|puzzle| puzzle.solver.prepare(&self.input, &self.params));
+
+        let prepared = match prepared {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                let e = e.in_day(day);
+
+                return expected
+                    .into_iter()
+                    .map(|(part, _)| check(part, Outcome::Error(e.clone())))
+                    .collect();
+            },
+        };
+
+        expected
+            .into_iter()
+            .map(|(part, expected)| {
+                let answer = match part {
+                    1 => prepared.part1(),
+                    _ => prepared.part2(),
+                };
+
+                let outcome = match answer {
+                    Err(e) => Outcome::Error(e.in_puzzle(day, part)),
+                    Ok(actual) if actual == expected => Outcome::Pass(actual),
+                    Ok(actual) => Outcome::Fail { expected, actual },
+                };
+
+                check(part, outcome)
+            })
+            .collect()
+    }
+}
+
+/// Loads the examples of a day, if it has any.
+pub fn load(dir: &Path, year: u16, day: u8) -> Result<Vec<Example>, String> {
+    let dir = day_dir(dir, year, day);
+    let path = dir.join("examples.json");
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let error = |e| format!("{}: {}", path.display(), e);
+
+    let manifest: Value = fs::read_to_string(&path)
+        .map_err(|e| error(e.to_string()))?
+        .parse()
+        .map_err(error)?;
+
+    let Value::Array(examples) = manifest else {
+        return Err(error("expected an array of examples".to_string()));
+    };
+
+    examples
+        .iter()
+        .map(|example| Example::from_json(year, day, example, &dir).map_err(error))
+        .collect()
+}
+
+/// Loads the examples of every day under `dir`, ordered by year and day.
+pub fn load_all(dir: &Path) -> Result<Vec<Example>, String> {
+    let entries = |dir: &Path| -> Result<Vec<String>, String> {
+        let mut names: Vec<_> = fs::read_dir(dir)
+            .map_err(|e| format!("{}: {}", dir.display(), e))?
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .collect();
+
+        names.sort();
+        Ok(names)
+    };
+
+    let mut examples = Vec::new();
+
+    for year in entries(dir)? {
+        let Ok(year) = year.parse::<u16>() else {
+            continue;
+        };
+
+        for day in entries(&dir.join(year.to_string()))? {
+            let Some(Ok(day)) = day.strip_prefix("day").map(str::parse) else {
+                continue;
+            };
+
+            examples.extend(load(dir, year, day)?);
+        }
+    }
+
+    Ok(examples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dir, load, Example};
+    use crate::{ Answer, Outcome };
+
+    #[test]
+    fn test_load() {
+        let examples = load(&dir(), 2024, 18).unwrap();
+
+        assert_eq!(examples[0].params.to_string(), "bytes=12,size=6");
+        assert_eq!(examples[0].answers, [Some(Answer::Integer(22)), None]);
+        assert_eq!(examples[1].answers, [None, Some(Answer::Coordinate(6, 1))]);
+        assert!(load(&dir(), 2024, 26).unwrap().is_empty());
+
+        let missing_input = r#"{"part1": 1}"#.parse().unwrap();
+        assert!(Example::from_json(2024, 18, &missing_input, &dir()).is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let mut example = load(&dir(), 2024, 18).unwrap().remove(0);
+        let [check] = example.check().try_into().unwrap();
+        assert_eq!(check.outcome, Outcome::Pass(Answer::Integer(22)));
+
+        example.answers[0] = Some(Answer::Integer(23));
+        assert!(example.check()[0].outcome.is_regression());
+    }
+}
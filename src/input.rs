@@ -0,0 +1,318 @@
+//! Puzzle inputs cached on disk, so solutions can run offline.
+//!
+//! Each input is stored as `<dir>/<year>/<day>.txt` next to a `.hash` file
+//! holding its FNV-1a hash, so an input changed by accident (an editor
+//! adding a trailing newline, say) is caught rather than silently solved.
+
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::{ Path, PathBuf };
+
+/// The cache directory, `$AOC_INPUTS` or `inputs` in the working directory.
+pub fn input_dir() -> PathBuf {
+    env::var_os("AOC_INPUTS").map_or_else(|| PathBuf::from("inputs"), PathBuf::from)
+}
+
+/// A hash of an input that is stable across builds, unlike std's hashers.
+pub fn hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+
+    format!("{:016x}", hash)
+}
+
+/// A source of puzzle inputs that are not cached yet.
+pub trait Fetcher {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, Box<dyn Error>>;
+}
+
+/// Downloads inputs from adventofcode.com.
+pub struct RudolfFetcher {
+    client: rudolf_rs::Client,
+}
+
+impl RudolfFetcher {
+    pub fn new(session_key: String) -> Self {
+        RudolfFetcher { client: rudolf_rs::Client::new(session_key) }
+    }
+
+    /// A fetcher using the session key in `$AOC_SESSION`, if set.
+    pub fn from_env() -> Option<Self> {
+        env::var("AOC_SESSION").ok().map(Self::new)
+    }
+}
+
+impl Fetcher for RudolfFetcher {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, Box<dyn Error>> {
+        self.client
+            .get(year, day)
+            .map_err(|e| format!("Could not download input: {:?}", e).into())
+    }
+}
+
+/// Reads inputs laid out like the cache from another directory, e.g. one
+/// shared between checkouts, or test data.
+pub struct DirFetcher {
+    dir: PathBuf,
+}
+
+impl DirFetcher {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        DirFetcher { dir: dir.into() }
+    }
+}
+
+impl Fetcher for DirFetcher {
+    fn fetch(&self, year: u16, day: u8) -> Result<String, Box<dyn Error>> {
+        let path = input_path(&self.dir, year, day);
+        fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e).into())
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Integrity {
+    /// the input matches its recorded hash
+    Verified,
+    /// no hash was recorded, e.g. for an input copied into the cache by hand
+    Unhashed,
+    Mismatch,
+}
+
+/// An input in the cache.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Cached {
+    pub year: u16,
+    pub day: u8,
+    pub path: PathBuf,
+    pub len: usize,
+    pub integrity: Integrity,
+}
+
+fn input_path(dir: &Path, year: u16, day: u8) -> PathBuf {
+    dir.join(year.to_string()).join(format!("{:02}.txt", day))
+}
+
+fn read_optional(path: &Path) -> io::Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+fn remove_optional(path: &Path) -> io::Result<bool> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// The cache of puzzle inputs, fetching missing inputs if given a fetcher.
+pub struct InputStore {
+    dir: PathBuf,
+    fetcher: Option<Box<dyn Fetcher>>,
+}
+
+impl InputStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        InputStore { dir: dir.into(), fetcher: None }
+    }
+
+    pub fn with_fetcher(mut self, fetcher: impl Fetcher + 'static) -> Self {
+        self.fetcher = Some(Box::new(fetcher));
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Where the input for a puzzle is cached, e.g. `inputs/2024/01.txt`.
+    pub fn path(&self, year: u16, day: u8) -> PathBuf {
+        input_path(&self.dir, year, day)
+    }
+
+    fn hash_path(&self, year: u16, day: u8) -> PathBuf {
+        self.path(year, day).with_extension("hash")
+    }
+
+    fn integrity(&self, year: u16, day: u8, input: &str) -> io::Result<Integrity> {
+        let integrity = match read_optional(&self.hash_path(year, day))? {
+            None => Integrity::Unhashed,
+            Some(recorded) if recorded.trim() == hash(input) => Integrity::Verified,
+            Some(_) => Integrity::Mismatch,
+        };
+
+        Ok(integrity)
+    }
+
+    /// Reads a cached input, or `None` if it has not been cached. An input
+    /// that does not match its hash is an error.
+    pub fn get_cached(&self, year: u16, day: u8) -> io::Result<Option<String>> {
+        let path = self.path(year, day);
+
+        let Some(input) = read_optional(&path)? else {
+            return Ok(None);
+        };
+
+        if self.integrity(year, day, &input)? == Integrity::Mismatch {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} has changed since it was cached; import or clear it", path.display()),
+            ));
+        }
+
+        Ok(Some(input))
+    }
+
+    /// Reads a cached input, fetching and caching it if needed.
+    pub fn get(&self, year: u16, day: u8) -> Result<String, Box<dyn Error>> {
+        if let Some(input) = self.get_cached(year, day)? {
+            return Ok(input);
+        }
+
+        let fetcher = self.fetcher.as_ref().ok_or_else(|| format!(
+            "No input cached at {} and nowhere to fetch it from",
+            self.path(year, day).display()
+        ))?;
+
+        let input = fetcher.fetch(year, day)?;
+        self.import(year, day, &input)?;
+
+        Ok(input)
+    }
+
+    /// Caches an input and its hash, replacing any cached before.
+    pub fn import(&self, year: u16, day: u8, input: &str) -> io::Result<()> {
+        let path = self.path(year, day);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(&path, input)?;
+        fs::write(self.hash_path(year, day), hash(input) + "\n")
+    }
+
+    /// The cached inputs, ordered by year and day, optionally of one year.
+    pub fn list(&self, year: Option<u16>) -> io::Result<Vec<Cached>> {
+        let mut cached = Vec::new();
+
+        let years = match fs::read_dir(&self.dir) {
+            Ok(years) => years,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(cached),
+            Err(e) => return Err(e),
+        };
+
+        for entry in years {
+            let entry = entry?;
+
+            let Some(entry_year) = entry.file_name().to_str().and_then(|y| y.parse().ok()) else {
+                continue;
+            };
+
+            if year.is_some_and(|year| year != entry_year) {
+                continue;
+            }
+
+            for entry in fs::read_dir(entry.path())? {
+                let path = entry?.path();
+
+                if path.extension().is_none_or(|extension| extension != "txt") {
+                    continue;
+                }
+
+                let Some(day) = path.file_stem().and_then(|d| d.to_str()?.parse().ok()) else {
+                    continue;
+                };
+
+                let input = fs::read_to_string(&path)?;
+
+                cached.push(Cached {
+                    year: entry_year,
+                    day,
+                    len: input.len(),
+                    integrity: self.integrity(entry_year, day, &input)?,
+                    path,
+                });
+            }
+        }
+
+        cached.sort_by_key(|cached| (cached.year, cached.day));
+        Ok(cached)
+    }
+
+    /// Removes the cached inputs of a year, or of one day, returning how
+    /// many were removed.
+    pub fn clear(&self, year: u16, day: Option<u8>) -> io::Result<usize> {
+        let mut removed = 0;
+
+        for cached in self.list(Some(year))? {
+            if day.is_some_and(|day| day != cached.day) {
+                continue;
+            }
+
+            fs::remove_file(&cached.path)?;
+            remove_optional(&self.hash_path(cached.year, cached.day))?;
+            removed += 1;
+        }
+
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{hash, DirFetcher, Integrity, InputStore};
+    use crate::temp::TempDir;
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash(""), "cbf29ce484222325");
+        assert_eq!(hash("a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn test_store() {
+        let root = TempDir::new("input");
+        let upstream = root.join("upstream");
+
+        fs::create_dir_all(upstream.join("2024")).unwrap();
+        fs::write(upstream.join("2024/01.txt"), "3   4\n").unwrap();
+
+        let store = InputStore::new(root.join("cache")).with_fetcher(DirFetcher::new(&upstream));
+
+        assert_eq!(store.get_cached(2024, 1).unwrap(), None);
+        assert_eq!(store.get(2024, 1).unwrap(), "3   4\n");
+        assert_eq!(store.get_cached(2024, 1).unwrap().as_deref(), Some("3   4\n"));
+        assert!(store.get(2024, 2).is_err());
+
+        store.import(2024, 2, "1\n").unwrap();
+        fs::write(store.path(2024, 3), "2\n").unwrap();
+        fs::write(store.path(2024, 2), "1").unwrap();
+
+        let integrity: Vec<_> = store
+            .list(None)
+            .unwrap()
+            .iter()
+            .map(|cached| (cached.day, cached.integrity))
+            .collect();
+
+        assert_eq!(integrity, [
+            (1, Integrity::Verified),
+            (2, Integrity::Mismatch),
+            (3, Integrity::Unhashed),
+        ]);
+        assert!(store.get_cached(2024, 2).is_err());
+
+        assert_eq!(store.clear(2024, Some(2)).unwrap(), 1);
+        assert_eq!(store.clear(2024, None).unwrap(), 2);
+        assert!(store.list(None).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,362 @@
+//! A minimal JSON value, enough to emit and read back our own output.
+
+use std::fmt::{ self, Display, Write };
+use std::str::FromStr;
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    /// A number, kept as its literal so large integers survive a round trip.
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Looks up `key` in an object.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(fields) => fields
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Number(n) => n.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => n.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+
+    f.write_char('"')
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => f.write_str("null"),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Number(n) => f.write_str(n),
+            Value::String(s) => write_string(f, s),
+            Value::Array(values) => {
+                f.write_char('[')?;
+
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+
+                f.write_char(']')
+            },
+            Value::Object(fields) => {
+                f.write_char('{')?;
+
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+
+                f.write_char('}')
+            },
+        }
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Value {
+                fn from(n: $t) -> Self {
+                    Value::Number(n.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl From<f64> for Value {
+    fn from(n: f64) -> Self {
+        if n.is_finite() {
+            Value::Number(n.to_string())
+        } else {
+            Value::Null
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Value::Null, Into::into)
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error<T>(&self, message: &str) -> Result<T, String> {
+        Err(format!("{} at offset {}", message, self.pos))
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn expect(&mut self, s: &str) -> Result<(), String> {
+        if self.input[self.pos..].starts_with(s) {
+            self.pos += s.len();
+            Ok(())
+        } else {
+            self.error(&format!("Expected '{}'", s))
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some('n') => self.expect("null").map(|_| Value::Null),
+            Some('t') => self.expect("true").map(|_| Value::Bool(true)),
+            Some('f') => self.expect("false").map(|_| Value::Bool(false)),
+            Some('"') => self.string().map(Value::String),
+            Some('[') => self.array(),
+            Some('{') => self.object(),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.number(),
+            _ => self.error("Expected a value"),
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, String> {
+        let rest = &self.input[self.pos..];
+        let len = rest
+            .find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c)))
+            .unwrap_or(rest.len());
+
+        let literal = &rest[..len];
+
+        if literal.parse::<f64>().is_err() {
+            return self.error("Invalid number");
+        }
+
+        self.pos += len;
+        Ok(Value::Number(literal.to_string()))
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect("\"")?;
+        let mut s = String::new();
+
+        loop {
+            let c = match self.peek() {
+                Some(c) => c,
+                None => return self.error("Unterminated string"),
+            };
+            self.pos += c.len_utf8();
+
+            match c {
+                '"' => return Ok(s),
+                '\\' => {
+                    let escaped = self.peek();
+                    self.pos += 1;
+
+                    match escaped {
+                        Some('"') => s.push('"'),
+                        Some('\\') => s.push('\\'),
+                        Some('/') => s.push('/'),
+                        Some('n') => s.push('\n'),
+                        Some('r') => s.push('\r'),
+                        Some('t') => s.push('\t'),
+                        Some('b') => s.push('\u{8}'),
+                        Some('f') => s.push('\u{c}'),
+                        Some('u') => {
+                            let hex = self
+                                .input
+                                .get(self.pos..self.pos + 4)
+                                .and_then(|h| u32::from_str_radix(h, 16).ok())
+                                .and_then(char::from_u32);
+
+                            match hex {
+                                Some(c) => s.push(c),
+                                None => return self.error("Invalid escape"),
+                            }
+                            self.pos += 4;
+                        },
+                        _ => return self.error("Invalid escape"),
+                    }
+                },
+                c => s.push(c),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, String> {
+        self.expect("[")?;
+        let mut values = Vec::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.pos += 1;
+            return Ok(Value::Array(values));
+        }
+
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(']') => {
+                    self.pos += 1;
+                    return Ok(Value::Array(values));
+                },
+                _ => return self.error("Expected ',' or ']'"),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, String> {
+        self.expect("{")?;
+        let mut fields = Vec::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(Value::Object(fields));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(":")?;
+            fields.push((key, self.value()?));
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some('}') => {
+                    self.pos += 1;
+                    return Ok(Value::Object(fields));
+                },
+                _ => return self.error("Expected ',' or '}'"),
+            }
+        }
+    }
+}
+
+impl FromStr for Value {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { input: s, pos: 0 };
+        let value = parser.value()?;
+
+        parser.skip_whitespace();
+        if parser.pos != s.len() {
+            return parser.error("Trailing characters");
+        }
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Value;
+
+    #[test]
+    fn test_round_trip() {
+        let value = Value::Object(vec![
+            ("year".to_string(), 2024.into()),
+            ("answer".to_string(), "4,6\n\"3\"".into()),
+            ("error".to_string(), Value::Null),
+            ("big".to_string(), Value::Number("123456789012345678901234567890".into())),
+            ("coord".to_string(), Value::Array(vec![6.into(), (-1).into()])),
+        ]);
+
+        let s = value.to_string();
+
+        assert_eq!(
+            s,
+            r#"{"year":2024,"answer":"4,6\n\"3\"","error":null,"big":123456789012345678901234567890,"coord":[6,-1]}"#
+        );
+        assert_eq!(s.parse::<Value>(), Ok(value));
+    }
+
+    #[test]
+    fn test_parse() {
+        let value: Value = r#" { "a" : [1, 2.5e3, true], "b": "é" } "#.parse().unwrap();
+
+        assert_eq!(value.get("b").and_then(Value::as_str), Some("é"));
+        assert_eq!(value.get("c"), None);
+        assert!("{\"a\":}".parse::<Value>().is_err());
+        assert!("[1] 2".parse::<Value>().is_err());
+    }
+}
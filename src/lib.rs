@@ -1,32 +1,225 @@
 #![feature(linked_list_cursors)]
-use std::fmt::Display;
+#[macro_use]
+mod registry;
 
-mod twenty_four;
+#[macro_use]
+pub mod diagnostics;
+
+pub mod fixtures;
+pub mod input;
+pub mod json;
+pub mod submit;
+#[doc(hidden)]
+pub mod temp;
+
+mod answer;
+mod error;
+mod params;
 mod util;
+mod verify;
+
+years! {
+    twenty_four,
+}
+
+pub use answer::Answer;
+pub use error::SolveError;
+pub use params::{ Param, Params };
+pub use registry::{ Puzzle, Year };
+pub use verify::{ verify, Answers, Check, Outcome };
+
+/// Puzzle input that has been parsed once, ready to solve either part.
+pub trait Prepared {
+    fn part1(&self) -> Result<Answer, SolveError>;
+    fn part2(&self) -> Result<Answer, SolveError>;
+}
 
 pub trait Solver {
-    fn part1(&self, input: &str) -> Box<dyn Display>;
-    fn part2(&self, input: &str) -> Box<dyn Display>;
+    /// Prepares `input` for solving, with `params` overriding the defaults
+    /// declared by `params()`.
+    fn prepare<'a>(
+        &self,
+        input: &'a str,
+        params: &Params,
+    ) -> Result<Box<dyn Prepared + 'a>, SolveError>;
+
+    fn answer_types(&self) -> (&'static str, &'static str);
+
+    fn params(&self) -> &'static [Param] {
+        &[]
+    }
+
+    /// Whether `prepare` parses the input, rather than each part parsing it
+    /// when solved.
+    fn parses_input(&self) -> bool {
+        true
+    }
+
+    fn part1(&self, input: &str) -> Result<Answer, SolveError> {
+        self.prepare(input, &Params::new())?.part1()
+    }
+
+    fn part2(&self, input: &str) -> Result<Answer, SolveError> {
+        self.prepare(input, &Params::new())?.part2()
+    }
+}
+
+/// A solution whose parts each parse the raw input themselves.
+pub struct Solution<T: Into<Answer>, U: Into<Answer>> {
+    part1: fn(&str) -> Result<T, SolveError>,
+    part2: fn(&str) -> Result<U, SolveError>,
+}
+
+struct Unparsed<'a, T, U> {
+    input: &'a str,
+    part1: fn(&str) -> Result<T, SolveError>,
+    part2: fn(&str) -> Result<U, SolveError>,
+}
+
+impl<T: Into<Answer>, U: Into<Answer>> Prepared for Unparsed<'_, T, U> {
+    fn part1(&self) -> Result<Answer, SolveError> {
+        (self.part1)(self.input).map(Into::into)
+    }
+
+    fn part2(&self) -> Result<Answer, SolveError> {
+        (self.part2)(self.input).map(Into::into)
+    }
+}
+
+impl<T, U> Solver for Solution<T, U>
+where T: Into<Answer> + 'static, U: Into<Answer> + 'static
+{
+    fn prepare<'a>(
+        &self,
+        input: &'a str,
+        params: &Params,
+    ) -> Result<Box<dyn Prepared + 'a>, SolveError> {
+        params.resolve(&[])?;
+
+        let Self { part1, part2 } = *self;
+        Ok(Box::new(Unparsed { input, part1, part2 }))
+    }
+
+    fn parses_input(&self) -> bool {
+        false
+    }
+
+    fn answer_types(&self) -> (&'static str, &'static str) {
+        (type_name::<T>(), type_name::<U>())
+    }
+}
+
+/// A solution that parses the input once into a `P` shared by both parts,
+/// for days where parsing (or work common to both parts) is expensive, or
+/// that depend on puzzle parameters.
+pub struct ParsedSolution<P, T: Into<Answer>, U: Into<Answer>> {
+    params: &'static [Param],
+    parse: fn(&str, &Params) -> Result<P, SolveError>,
+    part1: fn(&P) -> Result<T, SolveError>,
+    part2: fn(&P) -> Result<U, SolveError>,
+}
+
+impl<P, T: Into<Answer>, U: Into<Answer>> ParsedSolution<P, T, U> {
+    /// Parses `input` with `params` overriding the declared defaults.
+    pub fn parse(&self, input: &str, params: &Params) -> Result<P, SolveError> {
+        (self.parse)(input, &params.resolve(self.params)?)
+    }
+}
+
+struct Parsed<P, T, U> {
+    parsed: P,
+    part1: fn(&P) -> Result<T, SolveError>,
+    part2: fn(&P) -> Result<U, SolveError>,
 }
 
-pub struct Solution<T: Display, U: Display> {
-    part1: fn(&str) -> T,
-    part2: fn(&str) -> U,
+impl<P, T: Into<Answer>, U: Into<Answer>> Prepared for Parsed<P, T, U> {
+    fn part1(&self) -> Result<Answer, SolveError> {
+        (self.part1)(&self.parsed).map(Into::into)
+    }
+
+    fn part2(&self) -> Result<Answer, SolveError> {
+        (self.part2)(&self.parsed).map(Into::into)
+    }
 }
 
-impl<T: Display + 'static, U: Display + 'static> Solver for Solution<T, U> {
-    fn part1(&self, input: &str) -> Box<dyn Display> {
-        Box::new((self.part1)(input))
+impl<P, T, U> Solver for ParsedSolution<P, T, U>
+where P: 'static, T: Into<Answer> + 'static, U: Into<Answer> + 'static
+{
+    fn prepare<'a>(
+        &self,
+        input: &'a str,
+        params: &Params,
+    ) -> Result<Box<dyn Prepared + 'a>, SolveError> {
+        let parsed = self.parse(input, params)?;
+        let Self { part1, part2, .. } = *self;
+
+        Ok(Box::new(Parsed { parsed, part1, part2 }))
+    }
+
+    fn answer_types(&self) -> (&'static str, &'static str) {
+        (type_name::<T>(), type_name::<U>())
     }
 
-    fn part2(&self, input: &str) -> Box<dyn Display> {
-        Box::new((self.part2)(input))
+    fn params(&self) -> &'static [Param] {
+        self.params
     }
 }
 
-pub fn get_solution(year: usize, day: usize) -> &'static dyn Solver {
-    match year {
-        2024 => twenty_four::get_solution(day),
-        _ => panic!(),
+// strips the module path, e.g. `alloc::string::String` becomes `String`
+fn type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// The years with registered puzzles, in order.
+pub fn years() -> impl Iterator<Item = &'static Year> {
+    YEARS.iter()
+}
+
+pub fn get_year(year: u16) -> Option<&'static Year> {
+    years().find(|y| y.year == year)
+}
+
+/// All registered puzzles, ordered by year and day.
+pub fn puzzles() -> impl Iterator<Item = &'static Puzzle> {
+    years().flat_map(|year| year.puzzles.iter())
+}
+
+pub fn get_puzzle(year: u16, day: u8) -> Option<&'static Puzzle> {
+    puzzles().find(|puzzle| puzzle.year == year && puzzle.day == day)
+}
+
+pub fn get_solution(year: u16, day: u8) -> Option<&'static dyn Solver> {
+    get_puzzle(year, day).map(|puzzle| puzzle.solver)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_puzzle, get_solution, get_year, puzzles, years, Answer, Params, SolveError};
+
+    #[test]
+    fn test_registry() {
+        assert!(puzzles().is_sorted_by_key(|p| (p.year, p.day)));
+        assert_eq!(get_puzzle(2024, 16).unwrap().title, "Reindeer Maze");
+        assert_eq!(get_puzzle(2024, 17).unwrap().answer_types(), ("String", "usize"));
+        assert!(get_solution(2024, 26).is_none());
+        assert!(get_solution(2023, 1).is_none());
+        assert!(years().is_sorted_by_key(|year| year.year));
+        assert!(get_year(2024).unwrap().puzzles.iter().all(|p| p.year == 2024));
+        assert!(get_year(2023).is_none());
+    }
+
+    #[test]
+    fn test_prepare() {
+        let solution = get_solution(2024, 12).unwrap();
+        let input = "AAAA\nBBCD\nBBCC\nEEEC\n";
+        let prepared = solution.prepare(input, &Params::new()).unwrap();
+
+        assert_eq!(prepared.part1(), Ok(Answer::Integer(140)));
+        assert_eq!(prepared.part2(), Ok("80".parse().unwrap()));
+        assert_eq!(
+            solution.part1("AB\nC").err(),
+            Some(SolveError::from("Garden is not rectangular"))
+        );
     }
 }
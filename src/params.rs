@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+use std::fmt::{ self, Display };
+use std::str::FromStr;
+
+use crate::SolveError;
+
+/// A named puzzle constant, such as a grid size, that differs between the
+/// puzzle examples and the real input.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: i64,
+    pub help: &'static str,
+}
+
+/// Parameter values by name.
+///
+/// Callers build these as overrides; solvers receive them resolved against
+/// their declared `Param`s, so every declared name has a value.
+#[derive(PartialEq, Eq, Clone, Default, Debug)]
+pub struct Params {
+    values: BTreeMap<String, i64>,
+}
+
+impl Params {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, name: &str, value: i64) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    pub fn set(&mut self, name: &str, value: i64) {
+        self.values.insert(name.to_string(), value);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, i64)> {
+        self.values.iter().map(|(name, &value)| (name.as_str(), value))
+    }
+
+    /// Gets `name` converted to `T`, failing if it is missing or out of range.
+    pub fn get<T: TryFrom<i64>>(&self, name: &str) -> Result<T, SolveError> {
+        let &value = self
+            .values
+            .get(name)
+            .ok_or_else(|| format!("Missing parameter '{}'", name))?;
+
+        T::try_from(value)
+            .map_err(|_| format!("Parameter {}={} is out of range", name, value).into())
+    }
+
+    /// Applies these overrides to the `declared` defaults, rejecting names
+    /// that are not declared.
+    pub fn resolve(&self, declared: &[Param]) -> Result<Params, SolveError> {
+        if let Some(name) = self
+            .values
+            .keys()
+            .find(|name| !declared.iter().any(|p| p.name == name.as_str()))
+        {
+            return Err(format!("Unknown parameter '{}'", name).into());
+        }
+
+        let mut resolved = self.clone();
+
+        for param in declared {
+            resolved.values.entry(param.name.to_string()).or_insert(param.default);
+        }
+
+        Ok(resolved)
+    }
+}
+
+impl FromStr for Params {
+    type Err = SolveError;
+
+    /// Parses comma-separated `name=value` pairs, e.g. `width=11,height=7`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut params = Params::new();
+
+        for pair in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (name, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("Expected name=value, got '{}'", pair))?;
+
+            let value = value
+                .trim()
+                .parse()
+                .map_err(|e| format!("Parameter '{}': {}", name.trim(), e))?;
+
+            params.set(name.trim(), value);
+        }
+
+        Ok(params)
+    }
+}
+
+impl Display for Params {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pairs: Vec<_> = self
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+
+        write!(f, "{}", pairs.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Param, Params};
+
+    const DECLARED: &[Param] = &[
+        Param { name: "width", default: 101, help: "" },
+        Param { name: "height", default: 103, help: "" },
+    ];
+
+    #[test]
+    fn test_parse() {
+        let params: Params = "width=11, height=7".parse().unwrap();
+
+        assert_eq!(params, Params::new().with("width", 11).with("height", 7));
+        assert_eq!(params.to_string(), "height=7,width=11");
+        assert!("width".parse::<Params>().is_err());
+    }
+
+    #[test]
+    fn test_resolve() {
+        let params = Params::new().with("width", 11).resolve(DECLARED).unwrap();
+
+        assert_eq!(params.get::<isize>("width"), Ok(11));
+        assert_eq!(params.get::<isize>("height"), Ok(103));
+        assert!(params.get::<u8>("height").is_ok());
+        assert!(params.get::<u8>("depth").is_err());
+        assert!(Params::new().with("depth", 1).resolve(DECLARED).is_err());
+    }
+}
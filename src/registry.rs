@@ -0,0 +1,58 @@
+use crate::{ Param, Solver };
+
+/// A solution registered for a puzzle of a given year and day.
+pub struct Puzzle {
+    pub year: u16,
+    pub day: u8,
+    pub title: &'static str,
+    pub solver: &'static dyn Solver,
+}
+
+impl Puzzle {
+    /// The names of the types answering part 1 and part 2.
+    pub fn answer_types(&self) -> (&'static str, &'static str) {
+        self.solver.answer_types()
+    }
+
+    pub fn params(&self) -> &'static [Param] {
+        self.solver.params()
+    }
+}
+
+/// The puzzles registered for one year, ordered by day.
+pub struct Year {
+    pub year: u16,
+    pub puzzles: &'static [Puzzle],
+}
+
+/// Declares the day modules of a year and registers their `SOLUTION`s as the
+/// year's `YEAR`, so adding a day is a single line.
+macro_rules! puzzles {
+    ($year:literal; $($day:literal => $module:ident, $title:literal;)*) => {
+        $(mod $module;)*
+
+        pub const YEAR: $crate::Year = $crate::Year {
+            year: $year,
+            puzzles: &[
+                $(
+                    $crate::Puzzle {
+                        year: $year,
+                        day: $day,
+                        title: $title,
+                        solver: &$module::SOLUTION,
+                    },
+                )*
+            ],
+        };
+    };
+}
+
+/// Declares the year modules, each holding a `puzzles!` table, and registers
+/// them in `YEARS`. Years are listed in order.
+macro_rules! years {
+    ($($module:ident,)*) => {
+        $(mod $module;)*
+
+        const YEARS: &[$crate::Year] = &[$($module::YEAR,)*];
+    };
+}
@@ -0,0 +1,547 @@
+//! Submitting answers, and the history of what was submitted, which rules
+//! out answers that were rejected or are beyond ones that were too high or
+//! too low.
+//!
+//! The history is a JSON lines file with an entry per submission:
+//!
+//! ```text
+//! {"year":2024,"day":1,"part":1,"answer":1882714,"verdict":"too_low","timestamp":1734000000}
+//! ```
+
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::env;
+use std::error::Error;
+use std::fmt::{ self, Display };
+use std::fs::{ self, OpenOptions };
+use std::io::Write;
+use std::path::{ Path, PathBuf };
+use std::process::{ Command, Stdio };
+use std::time::SystemTime;
+
+use crate::json::Value;
+use crate::{ Answer, Answers };
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// submitted too soon after a wrong answer, so not checked
+    RateLimited,
+}
+
+impl Verdict {
+    /// Whether the answer is known to be wrong.
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Self::Wrong | Self::TooHigh | Self::TooLow)
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Right => "right",
+            Self::Wrong => "wrong",
+            Self::TooHigh => "too_high",
+            Self::TooLow => "too_low",
+            Self::RateLimited => "rate_limited",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [Self::Right, Self::Wrong, Self::TooHigh, Self::TooLow, Self::RateLimited]
+            .into_iter()
+            .find(|verdict| verdict.name() == name)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name().replace('_', " "))
+    }
+}
+
+/// Somewhere to submit answers.
+pub trait Backend {
+    fn submit(&self, year: u16, day: u8, part: u8, answer: &Answer) -> Result<Verdict, Box<dyn Error>>;
+}
+
+/// Submits answers to adventofcode.com, or a server like it, with `curl`,
+/// which must be on the `PATH`: the crate has no HTTPS client of its own, and
+/// rudolf-rs only downloads inputs.
+pub struct HttpBackend {
+    url: String,
+    session_key: String,
+}
+
+impl HttpBackend {
+    pub fn new(url: &str, session_key: String) -> Self {
+        HttpBackend { url: url.trim_end_matches('/').to_string(), session_key }
+    }
+
+    /// A backend using the session key in `$AOC_SESSION`, if set, and the
+    /// server in `$AOC_URL`, if not adventofcode.com.
+    pub fn from_env() -> Option<Self> {
+        let url = env::var("AOC_URL").unwrap_or("https://adventofcode.com".to_string());
+        env::var("AOC_SESSION").ok().map(|key| Self::new(&url, key))
+    }
+}
+
+impl Backend for HttpBackend {
+    fn submit(&self, year: u16, day: u8, part: u8, answer: &Answer) -> Result<Verdict, Box<dyn Error>> {
+        let url = format!("{}/{}/day/{}/answer", self.url, year, day);
+
+        // the session key goes through stdin to keep it out of the process list
+        let mut curl = Command::new("curl")
+            .args(["--silent", "--show-error", "--fail", "--config", "-"])
+            .args(["--data", &format!("level={}", part)])
+            .args(["--data-urlencode", &format!("answer={}", answer)])
+            .arg(&url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Could not run curl, which submitting needs: {}", e))?;
+
+        if let Some(mut stdin) = curl.stdin.take() {
+            writeln!(stdin, "cookie = \"session={}\"", self.session_key)?;
+        }
+
+        let output = curl.wait_with_output()?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("Could not submit to {}: {}", url, error.trim()).into());
+        }
+
+        Ok(parse_response(&String::from_utf8_lossy(&output.stdout))?)
+    }
+}
+
+/// Reads the verdict from the page returned for a submission.
+pub fn parse_response(html: &str) -> Result<Verdict, String> {
+    if html.contains("That's the right answer") {
+        Ok(Verdict::Right)
+    } else if html.contains("You gave an answer too recently") {
+        Ok(Verdict::RateLimited)
+    } else if html.contains("That's not the right answer") {
+        if html.contains("your answer is too high") {
+            Ok(Verdict::TooHigh)
+        } else if html.contains("your answer is too low") {
+            Ok(Verdict::TooLow)
+        } else {
+            Ok(Verdict::Wrong)
+        }
+    } else if html.contains("You don't seem to be solving the right level") {
+        Err("That part is already solved, or not unlocked yet".to_string())
+    } else {
+        Err("Unrecognised response to the submission".to_string())
+    }
+}
+
+/// Judges answers against known ones, for tests.
+#[derive(Default)]
+pub struct MemoryBackend {
+    answers: Answers,
+    /// every answer submitted, in order
+    pub submitted: RefCell<Vec<(u16, u8, u8, Answer)>>,
+}
+
+impl MemoryBackend {
+    pub fn new(answers: Answers) -> Self {
+        MemoryBackend { answers, submitted: RefCell::default() }
+    }
+}
+
+impl Backend for MemoryBackend {
+    fn submit(&self, year: u16, day: u8, part: u8, answer: &Answer) -> Result<Verdict, Box<dyn Error>> {
+        self.submitted.borrow_mut().push((year, day, part, answer.clone()));
+
+        let expected = self
+            .answers
+            .get(year, day, part)
+            .ok_or_else(|| format!("No answer to {} day {} part {}", year, day, part))?;
+
+        let verdict = match answer.cmp_numeric(expected) {
+            _ if answer == expected => Verdict::Right,
+            Some(Ordering::Greater) => Verdict::TooHigh,
+            Some(Ordering::Less) => Verdict::TooLow,
+            _ => Verdict::Wrong,
+        };
+
+        Ok(verdict)
+    }
+}
+
+/// What the rejected guesses at a part say about its answer.
+#[derive(PartialEq, Eq, Clone, Default, Debug)]
+pub struct Bounds {
+    /// the largest guess that was too low
+    pub lower: Option<Answer>,
+    /// the smallest guess that was too high
+    pub upper: Option<Answer>,
+    pub rejected: Vec<Answer>,
+}
+
+impl Bounds {
+    /// Why `answer` cannot be right, if the guesses rule it out.
+    pub fn check(&self, answer: &Answer) -> Option<String> {
+        if self.rejected.contains(answer) {
+            return Some(format!("{} was already rejected", answer));
+        }
+
+        if let Some(lower) = &self.lower {
+            if answer.cmp_numeric(lower).is_some_and(Ordering::is_le) {
+                return Some(format!("{} is too low, as {} was too low", answer, lower));
+            }
+        }
+
+        if let Some(upper) = &self.upper {
+            if answer.cmp_numeric(upper).is_some_and(Ordering::is_ge) {
+                return Some(format!("{} is too high, as {} was too high", answer, upper));
+            }
+        }
+
+        None
+    }
+}
+
+impl Display for Bounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.lower, &self.upper) {
+            (Some(lower), Some(upper)) => write!(f, "between {} and {}", lower, upper),
+            (Some(lower), None) => write!(f, "above {}", lower),
+            (None, Some(upper)) => write!(f, "below {}", upper),
+            (None, None) => write!(f, "unbounded"),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Submission {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: Answer,
+    pub verdict: Verdict,
+    pub timestamp: u64,
+}
+
+impl Submission {
+    pub fn new(year: u16, day: u8, part: u8, answer: Answer, verdict: Verdict) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |time| time.as_secs());
+
+        Submission { year, day, part, answer, verdict, timestamp }
+    }
+
+    fn to_json(&self) -> Value {
+        Value::Object(vec![
+            ("year".to_string(), self.year.into()),
+            ("day".to_string(), self.day.into()),
+            ("part".to_string(), self.part.into()),
+            ("answer".to_string(), self.answer.to_json()),
+            ("verdict".to_string(), self.verdict.name().into()),
+            ("timestamp".to_string(), self.timestamp.into()),
+        ])
+    }
+
+    fn from_json(value: &Value) -> Result<Self, String> {
+        let int = |name: &str| value
+            .get(name)
+            .and_then(Value::as_i64)
+            .ok_or_else(|| format!("Missing or invalid field '{}'", name));
+
+        let answer = value
+            .get("answer")
+            .and_then(Answer::from_json)
+            .ok_or("Missing or invalid field 'answer'")?;
+
+        let verdict = value
+            .get("verdict")
+            .and_then(Value::as_str)
+            .and_then(Verdict::from_name)
+            .ok_or("Missing or invalid field 'verdict'")?;
+
+        Ok(Submission {
+            year: int("year")?.try_into().map_err(|_| "Invalid year")?,
+            day: int("day")?.try_into().map_err(|_| "Invalid day")?,
+            part: int("part")?.try_into().map_err(|_| "Invalid part")?,
+            answer,
+            verdict,
+            timestamp: int("timestamp")?.try_into().unwrap_or_default(),
+        })
+    }
+}
+
+/// Every answer submitted, oldest first.
+#[derive(PartialEq, Eq, Clone, Default, Debug)]
+pub struct Submissions {
+    submissions: Vec<Submission>,
+}
+
+impl Submissions {
+    /// The history file, `$AOC_SUBMISSIONS` or `submissions.jsonl` in the
+    /// working directory.
+    pub fn path() -> PathBuf {
+        env::var_os("AOC_SUBMISSIONS")
+            .map_or_else(|| PathBuf::from("submissions.jsonl"), PathBuf::from)
+    }
+
+    /// Loads the history from `path`, which may not exist yet.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let history = fs::read_to_string(path)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        let submissions = history
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| line
+                .parse()
+                .and_then(|value| Submission::from_json(&value))
+                .map_err(|e| format!("{}:{}: {}", path.display(), i + 1, e))
+            )
+            .collect::<Result<_, _>>()?;
+
+        Ok(Submissions { submissions })
+    }
+
+    /// Records a submission, appending it to the file at `path`.
+    pub fn append(&mut self, path: &Path, submission: Submission) -> Result<(), String> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        writeln!(file, "{}", submission.to_json())
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        self.submissions.push(submission);
+        Ok(())
+    }
+
+    /// The submissions to one part, oldest first.
+    pub fn part(&self, year: u16, day: u8, part: u8) -> impl Iterator<Item = &Submission> {
+        self.submissions
+            .iter()
+            .filter(move |s| (s.year, s.day, s.part) == (year, day, part))
+    }
+
+    /// The answer accepted for a part, if any.
+    pub fn accepted(&self, year: u16, day: u8, part: u8) -> Option<&Submission> {
+        self.part(year, day, part).find(|s| s.verdict == Verdict::Right)
+    }
+
+    /// The latest verdict on a particular answer to a part, ignoring
+    /// submissions that were rate limited.
+    pub fn verdict(&self, year: u16, day: u8, part: u8, answer: &Answer) -> Option<&Submission> {
+        self.part(year, day, part)
+            .filter(|s| s.answer == *answer && s.verdict != Verdict::RateLimited)
+            .last()
+    }
+
+    /// The bounds implied by the guesses at a part.
+    pub fn bounds(&self, year: u16, day: u8, part: u8) -> Bounds {
+        let mut bounds = Bounds::default();
+
+        for submission in self.part(year, day, part).filter(|s| s.verdict.is_wrong()) {
+            let answer = &submission.answer;
+
+            let bound = match submission.verdict {
+                Verdict::TooLow => Some((&mut bounds.lower, Ordering::Greater)),
+                Verdict::TooHigh => Some((&mut bounds.upper, Ordering::Less)),
+                _ => None,
+            };
+
+            // keep the tightest bound
+            if let Some((bound, tighter)) = bound {
+                let replace = bound
+                    .as_ref()
+                    .is_none_or(|bound| answer.cmp_numeric(bound) == Some(tighter));
+
+                if replace {
+                    *bound = Some(answer.clone());
+                }
+            }
+
+            if !bounds.rejected.contains(answer) {
+                bounds.rejected.push(answer.clone());
+            }
+        }
+
+        bounds
+    }
+
+    /// Submits an answer through `backend` and records the verdict in the
+    /// file at `path`, refusing answers that earlier guesses rule out.
+    pub fn submit(
+        &mut self,
+        path: &Path,
+        backend: &dyn Backend,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: Answer,
+    ) -> Result<Verdict, Box<dyn Error>> {
+        if let Some(reason) = self.bounds(year, day, part).check(&answer) {
+            return Err(reason.into());
+        }
+
+        let verdict = backend.submit(year, day, part, &answer)?;
+        self.append(path, Submission::new(year, day, part, answer, verdict))?;
+
+        Ok(verdict)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{ Read, Write };
+    use std::net::TcpListener;
+    use std::process::Command;
+    use std::thread;
+
+    use super::{
+        parse_response, Backend, Bounds, HttpBackend, MemoryBackend, Submission, Submissions,
+        Verdict,
+    };
+    use crate::temp::TempDir;
+    use crate::Answer;
+
+    #[test]
+    fn test_parse_response() {
+        let page = |message| format!("<main><article><p>{}</p></article></main>", message);
+
+        assert_eq!(parse_response(&page("That's the right answer! You are one gold star")), Ok(Verdict::Right));
+        assert_eq!(
+            parse_response(&page("That's not the right answer; your answer is too high.")),
+            Ok(Verdict::TooHigh)
+        );
+        assert_eq!(parse_response(&page("That's not the right answer.")), Ok(Verdict::Wrong));
+        assert_eq!(
+            parse_response(&page("You gave an answer too recently; you have 42s left to wait.")),
+            Ok(Verdict::RateLimited)
+        );
+        assert!(parse_response("<html></html>").is_err());
+    }
+
+    #[test]
+    fn test_memory_backend() {
+        let backend = MemoryBackend::new(r#"{"2024": {"1": {"part1": 11}}}"#.parse().unwrap());
+        let submit = |answer: i64| backend.submit(2024, 1, 1, &answer.into()).unwrap();
+
+        assert_eq!(submit(12), Verdict::TooHigh);
+        assert_eq!(submit(10), Verdict::TooLow);
+        assert_eq!(submit(11), Verdict::Right);
+        assert_eq!(backend.submitted.borrow().len(), 3);
+        assert!(backend.submit(2024, 1, 2, &11.into()).is_err());
+    }
+
+    #[test]
+    fn test_http_backend() {
+        if Command::new("curl").arg("--version").output().is_err() {
+            eprintln!("skipping test_http_backend: curl is not installed");
+            return;
+        }
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+
+            // read until the form body, which ends the request
+            while !String::from_utf8_lossy(&request).contains("answer=") {
+                let n = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..n]);
+            }
+
+            let body = "<article><p>That's not the right answer; your answer is too low.</p></article>";
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            ).unwrap();
+
+            String::from_utf8(request).unwrap()
+        });
+
+        let backend = HttpBackend::new(&url, "abc123".to_string());
+        let verdict = backend.submit(2024, 1, 2, &Answer::from(31u8)).unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(verdict, Verdict::TooLow);
+        assert!(request.starts_with("POST /2024/day/1/answer "));
+        assert!(request.contains("Cookie: session=abc123"));
+        assert!(request.contains("level=2&answer=31"));
+    }
+
+    #[test]
+    fn test_history() {
+        let dir = TempDir::new("submissions");
+        let path = dir.join("submissions.jsonl");
+        let mut history = Submissions::default();
+
+        history.append(&path, Submission::new(2024, 1, 1, 12.into(), Verdict::TooHigh)).unwrap();
+        history.append(&path, Submission::new(2024, 1, 1, 11.into(), Verdict::RateLimited)).unwrap();
+        history.append(&path, Submission::new(2024, 1, 1, 11.into(), Verdict::Right)).unwrap();
+
+        let loaded = Submissions::load(&path).unwrap();
+
+        assert_eq!(loaded, history);
+        assert_eq!(loaded.verdict(2024, 1, 1, &12.into()).unwrap().verdict, Verdict::TooHigh);
+        assert_eq!(loaded.verdict(2024, 1, 1, &11.into()).unwrap().verdict, Verdict::Right);
+        assert_eq!(loaded.accepted(2024, 1, 1).unwrap().answer, Answer::from(11));
+        assert!(loaded.accepted(2024, 1, 2).is_none());
+    }
+
+    #[test]
+    fn test_submit() {
+        let dir = TempDir::new("submit");
+        let path = dir.join("submissions.jsonl");
+        let backend = MemoryBackend::new(r#"{"2024": {"1": {"part1": 11}}}"#.parse().unwrap());
+        let mut history = Submissions::default();
+        let mut submit = |answer: i64| history.submit(&path, &backend, 2024, 1, 1, answer.into());
+
+        assert_eq!(submit(12).unwrap(), Verdict::TooHigh);
+        assert!(submit(12).is_err());
+        assert!(submit(13).is_err());
+        assert_eq!(submit(11).unwrap(), Verdict::Right);
+        assert_eq!(backend.submitted.borrow().len(), 2);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut history = Submissions::default();
+        let guesses = [(50, Verdict::TooHigh), (20, Verdict::TooLow), (30, Verdict::TooHigh),
+            (10, Verdict::TooLow), (25, Verdict::Wrong), (40, Verdict::RateLimited)];
+
+        for (answer, verdict) in guesses {
+            history.submissions.push(Submission::new(2024, 1, 1, answer.into(), verdict));
+        }
+
+        let bounds = history.bounds(2024, 1, 1);
+
+        assert_eq!(bounds.lower, Some(20.into()));
+        assert_eq!(bounds.upper, Some(30.into()));
+        assert_eq!(bounds.rejected.len(), 5);
+        assert_eq!(bounds.to_string(), "between 20 and 30");
+
+        assert_eq!(bounds.check(&25.into()).unwrap(), "25 was already rejected");
+        assert_eq!(bounds.check(&35.into()).unwrap(), "35 is too high, as 30 was too high");
+        assert_eq!(bounds.check(&Answer::from(15u8)).unwrap(), "15 is too low, as 20 was too low");
+        assert_eq!(bounds.check(&27.into()), None);
+        assert_eq!(bounds.check(&"abc".into()), None);
+        assert_eq!(history.bounds(2024, 1, 2), Bounds::default());
+    }
+}
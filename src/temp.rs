@@ -0,0 +1,44 @@
+//! Scratch directories for tests that touch the filesystem.
+
+use std::env;
+use std::fs;
+use std::ops::Deref;
+use std::path::{ Path, PathBuf };
+use std::process;
+use std::sync::atomic::{ AtomicUsize, Ordering };
+
+/// A fresh directory under the system's temp dir, removed with its contents
+/// when dropped, so a failing assert doesn't leave it behind.
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// Creates a directory named after `name`, unique to this process and
+    /// call, so tests running in parallel don't share one.
+    pub fn new(name: &str) -> Self {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        let n = COUNT.fetch_add(1, Ordering::Relaxed);
+        let path = env::temp_dir().join(format!("aoc-{}-{}-{}", name, process::id(), n));
+
+        fs::create_dir_all(&path)
+            .unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+
+        TempDir { path }
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
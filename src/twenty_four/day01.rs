@@ -1,66 +1,48 @@
-use crate::Solution;
+use crate::{ Solution, SolveError };
+use crate::util::parse;
 use std::collections::HashMap;
 use std::iter::zip;
 
 pub const SOLUTION: Solution<usize, usize> = Solution { part1, part2 };
 
-fn parse(input: &str) -> (Vec<usize>, Vec<usize>) {
+fn parse(input: &str) -> Result<(Vec<usize>, Vec<usize>), SolveError> {
     let mut left: Vec<usize> = Vec::new();
     let mut right: Vec<usize> = Vec::new();
 
-    input.lines().for_each(|line| {
-        let mut iter = line.split_whitespace().map(|s| s.parse().unwrap());
+    for line in input.lines() {
+        let mut iter = line
+            .split_whitespace()
+            .map(|s| parse::token(input, s));
 
-        let a = iter.next().unwrap();
-        let b = iter.next().unwrap();
+        let missing = || SolveError::at(input, line, "expected two numbers");
+        let a = iter.next().ok_or_else(missing)??;
+        let b = iter.next().ok_or_else(missing)??;
 
         left.push(a);
         right.push(b);
-    });
+    }
 
-    (left, right)
+    Ok((left, right))
 }
 
-fn part1(input: &str) -> usize {
-    let (mut left, mut right) = parse(input);
+fn part1(input: &str) -> Result<usize, SolveError> {
+    let (mut left, mut right) = parse(input)?;
 
     left.sort();
     right.sort();
 
     let pairs = zip(left, right);
 
-    pairs.map(|(a, b)| a.abs_diff(b)).sum()
+    Ok(pairs.map(|(a, b)| a.abs_diff(b)).sum())
 }
 
-fn part2(input: &str) -> usize {
-    let (left, right) = parse(input);
+fn part2(input: &str) -> Result<usize, SolveError> {
+    let (left, right) = parse(input)?;
     let mut freqs: HashMap<usize, usize> = HashMap::new();
 
     for e in right {
         *freqs.entry(e).or_insert(0) += 1;
     }
 
-    left.iter().map(|e| e * freqs.get(e).unwrap_or(&0)).sum()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::{part1, part2};
-
-    const TEST_INPUT: &str = "3   4
-    4   3
-    2   5
-    1   3
-    3   9
-    3   3";
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 11);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(TEST_INPUT), 31);
-    }
+    Ok(left.iter().map(|e| e * freqs.get(e).unwrap_or(&0)).sum())
 }
@@ -1,13 +1,14 @@
-use crate::Solution;
+use crate::{ Solution, SolveError };
+use crate::util::parse;
 
 pub const SOLUTION: Solution<usize, usize> = Solution { part1, part2 };
 
-fn parse(input: &str) -> Vec<Vec<u8>> {
+fn parse(input: &str) -> Result<Vec<Vec<u8>>, SolveError> {
     input
         .lines()
         .map(|l| l
             .split_whitespace()
-            .map(|s| s.parse().unwrap())
+            .map(|s| parse::token(input, s))
             .collect()
         )
         .collect()
@@ -25,19 +26,21 @@ fn is_safe(report: &[u8]) -> bool {
         })
 }
 
-fn part1(input: &str) -> usize {
-    let reports = parse(input);
+fn part1(input: &str) -> Result<usize, SolveError> {
+    let reports = parse(input)?;
 
-    reports
+    let safe = reports
         .iter()
         .filter(|r| is_safe(r))
-        .count()
+        .count();
+
+    Ok(safe)
 }
 
-fn part2(input: &str) -> usize {
-    let reports = parse(input);
+fn part2(input: &str) -> Result<usize, SolveError> {
+    let reports = parse(input)?;
 
-    reports
+    let safe = reports
         .iter()
         .map(|report| {
             // brute force: consider original report
@@ -54,27 +57,7 @@ fn part2(input: &str) -> usize {
             combinations
         })
         .filter(|rs| rs.iter().any(|r| is_safe(r)))
-        .count()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::{part1, part2};
-
-    const TEST_INPUT: &str = "7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9";
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 2);
-    }
+        .count();
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(TEST_INPUT), 4);
-    }
+    Ok(safe)
 }
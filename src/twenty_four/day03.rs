@@ -1,9 +1,10 @@
-use crate::Solution;
+use crate::{ Solution, SolveError };
+use crate::util::parse;
 use regex::Regex;
 
 pub const SOLUTION: Solution<usize, usize> = Solution { part1, part2 };
 
-fn part1(input: &str) -> usize {
+fn part1(input: &str) -> Result<usize, SolveError> {
     let re = Regex::new(r"mul\(([0-9]+),([0-9]+)\)").unwrap();
 
     re
@@ -11,13 +12,14 @@ fn part1(input: &str) -> usize {
         .map(|s| s
             .iter()
             .skip(1)
-            .map(|n| n.unwrap().as_str().parse::<usize>().unwrap())
-            .product::<usize>()
+            .flatten()
+            .map(|n| parse::token::<usize>(input, n.as_str()))
+            .product::<Result<usize, _>>()
         )
         .sum()
 }
 
-fn part2(input: &str) -> usize {
+fn part2(input: &str) -> Result<usize, SolveError> {
     let re = Regex::new(r"mul\(([0-9]+),([0-9]+)\)").unwrap();
 
     input
@@ -30,28 +32,11 @@ fn part2(input: &str) -> usize {
                 .map(|s| s
                     .iter()
                     .skip(1)
-                    .map(|n| n.unwrap().as_str().parse::<usize>().unwrap())
-                    .product::<usize>()
+                    .flatten()
+                    .map(|n| parse::token::<usize>(input, n.as_str()))
+                    .product::<Result<usize, _>>()
                 )
-                .sum::<usize>()
+                .sum::<Result<usize, _>>()
         })
         .sum()
 }
-
-#[cfg(test)]
-mod tests {
-    use super::{part1, part2};
-
-    const TEST_INPUT_1: &str ="xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
-    const TEST_INPUT_2: &str ="xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(TEST_INPUT_1), 161);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(TEST_INPUT_2), 48);
-    }
-}
@@ -1,4 +1,4 @@
-use crate::Solution;
+use crate::{ Solution, SolveError };
 use std::ops::{ Add, Mul };
 
 pub const SOLUTION: Solution<usize, usize> = Solution { part1, part2 };
@@ -32,15 +32,10 @@ impl Mul<isize> for Point {
 }
 
 fn grid_get<'a, T>(grid: &[&'a [T]], pos: Point) -> Option<&'a T> {
-    let height = grid.len().try_into().expect("Grid height overflow");
-    let width = grid[0].len().try_into().expect("Grid height overflow");
-
-    if pos.x >= 0 && pos.x < width && pos.y >= 0 && pos.y < height {
-        Some(&grid[pos.y as usize][pos.x as usize])
-    } else {
-        None
-    }
+    let x = usize::try_from(pos.x).ok()?;
+    let y = usize::try_from(pos.y).ok()?;
 
+    grid.get(y)?.get(x)
 }
 
 fn directed_search<T: Eq>(
@@ -99,34 +94,46 @@ fn is_cross<T: Eq>(grid: &[&[T]], pattern: &[T], start: Point) -> bool {
     (directions[0] || directions[2]) && (directions[1] || directions[3])
 }
 
-fn parse_grid(input: &str) -> Vec<&[u8]> {
-    input
+fn parse_grid(input: &str) -> Result<Vec<&[u8]>, SolveError> {
+    let grid: Vec<_> = input
         .lines()
         .map(|l| l.as_bytes())
-        .collect()
+        .collect();
+
+    let width = grid.first().map_or(0, |row| row.len());
+
+    if width == 0 {
+        return Err("Empty grid".into());
+    }
+
+    if let Some(line) = input.lines().find(|l| l.len() != width) {
+        return Err(SolveError::at(input, line, format!("expected {} columns", width)));
+    }
+
+    Ok(grid)
 }
 
-fn part1(input: &str) -> usize {
-    let grid = parse_grid(input);
+fn part1(input: &str) -> Result<usize, SolveError> {
+    let grid = parse_grid(input)?;
     let mut count = 0;
 
     for y in 0..grid.len() {
-        for x in 0..grid[0].len() {
+        for x in 0..grid[y].len() {
             let pos = Point { x: x as isize, y: y as isize };
 
             count += search(&grid, "XMAS".as_bytes(), pos);
         }
     }
 
-    count
+    Ok(count)
 }
 
-fn part2(input: &str) -> usize {
-    let grid = parse_grid(input);
+fn part2(input: &str) -> Result<usize, SolveError> {
+    let grid = parse_grid(input)?;
     let mut count = 0;
 
     for y in 0..grid.len() {
-        for x in 0..grid[0].len() {
+        for x in 0..grid[y].len() {
             let pos = Point { x: x as isize, y: y as isize };
 
             if is_cross(&grid, "MAS".as_bytes(), pos) {
@@ -135,27 +142,18 @@ fn part2(input: &str) -> usize {
         }
     }
 
-    count
+    Ok(count)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &str = "MMMSXXMASM
-MSAMXMSMSA
-AMXSXMAAMM
-MSAMASMSMX
-XMASAMXAMM
-XXAMMXXAMA
-SMSMSASXSS
-SAXAMASAAA
-MAMMMXMMMM
-MXMXAXMASX";
+    const TEST_INPUT: &str = include_str!("../../fixtures/2024/day04/example.txt");
 
     #[test]
     fn test_search() {
-        let grid = parse_grid(TEST_INPUT);
+        let grid = parse_grid(TEST_INPUT).unwrap();
         let count = search(&grid, "XMAS".as_bytes(), Point { x: 4, y: 1 });
 
         assert_eq!(count, 1);
@@ -163,17 +161,13 @@ MXMXAXMASX";
 
     #[test]
     fn test_search_part2() {
-        let grid = parse_grid(TEST_INPUT);
+        let grid = parse_grid(TEST_INPUT).unwrap();
         assert!(is_cross(&grid, "MAS".as_bytes(), Point { x: 7, y: 2 }));
     }
 
     #[test]
-    fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 18);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(TEST_INPUT), 9);
+    fn test_parse_grid() {
+        assert!(parse_grid("XMAS\nX\nXMAS").is_err());
+        assert!(parse_grid("").is_err());
     }
 }
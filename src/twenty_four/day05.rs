@@ -1,10 +1,14 @@
-use crate::Solution;
+use crate::{ Solution, SolveError };
+use crate::util::parse;
 use std::hash::Hash;
 use std::collections::{HashMap, HashSet};
 use std::cmp::Ordering;
 
 pub const SOLUTION: Solution<usize, usize> = Solution { part1, part2 };
 
+type Rules = Vec<(usize, usize)>;
+type Updates = Vec<Vec<usize>>;
+
 struct SleighSafetyManual<T: Eq + Hash> {
     // maps each page to the set of pages that must not come after it
     rule_map: HashMap<T, HashSet<T>>,
@@ -42,93 +46,58 @@ impl<T: Eq + Hash + Clone + Copy> SleighSafetyManual<T> {
     }
 }
 
-fn parse_rules(input: &str) -> Vec<(usize, usize)> {
-    input
+fn parse_rules(
+    input: &str,
+    rules_str: &str,
+) -> Result<Rules, SolveError> {
+    rules_str
         .lines()
-        .map(|l| l
-            .split_once('|')
-            .map(|(a, b)| (a.parse().unwrap(), b.parse().unwrap()))
-            .unwrap()
-        )
+        .map(|l| {
+            let (a, b) = parse::split_once(input, l, "|")?;
+            Ok((parse::token(input, a)?, parse::token(input, b)?))
+        })
         .collect()
 }
 
-fn parse_updates(input: &str) -> Vec<Vec<usize>> {
-    input
+fn parse_updates(
+    input: &str,
+    updates_str: &str,
+) -> Result<Updates, SolveError> {
+    updates_str
         .lines()
-        .map(|l| l.split(',').map(|n| n.parse().unwrap()).collect())
+        .map(|l| l.split(',').map(|n| parse::token(input, n)).collect())
         .collect()
 }
 
-fn parse_input(input: &str) -> (Vec<(usize,usize)>, Vec<Vec<usize>>) {
-    let (rules_str, updates_str) = input.split_once("\n\n").unwrap();
+fn parse_input(input: &str) -> Result<(Rules, Updates), SolveError> {
+    let (rules_str, updates_str) = parse::split_once(input, input, "\n\n")?;
 
-    (parse_rules(rules_str), parse_updates(updates_str))
+    Ok((parse_rules(input, rules_str)?, parse_updates(input, updates_str)?))
 }
 
-fn part1(input: &str) -> usize {
-    let (rules, updates) = parse_input(input);
+fn part1(input: &str) -> Result<usize, SolveError> {
+    let (rules, updates) = parse_input(input)?;
     let manual = SleighSafetyManual::from_rules(&rules);
 
-    updates
+    let sum = updates
         .iter()
         .filter(|update| manual.validate(update))
         .map(|update| update[update.len() / 2])
-        .sum()
+        .sum();
+
+    Ok(sum)
 }
 
-fn part2(input: &str) -> usize {
-    let (rules, updates) = parse_input(input);
+fn part2(input: &str) -> Result<usize, SolveError> {
+    let (rules, updates) = parse_input(input)?;
     let manual = SleighSafetyManual::from_rules(&rules);
 
-    updates
+    let sum = updates
         .iter()
         .filter(|update| !manual.validate(update))
         .map(|update| manual.repair(update))
         .map(|update| update[update.len() / 2])
-        .sum()
-}
+        .sum();
 
-#[cfg(test)]
-mod tests {
-    use super::{part1, part2};
-
-    const TEST_INPUT: &str = "47|53
-97|13
-97|61
-97|47
-75|29
-61|13
-75|53
-29|13
-97|29
-53|29
-61|53
-97|53
-61|29
-47|13
-75|47
-97|75
-47|61
-75|61
-47|29
-75|13
-53|13
-
-75,47,61,53,29
-97,61,53,29,13
-75,29,13
-75,97,47,61,53
-61,13,29
-97,13,75,29,47";
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 143);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(TEST_INPUT), 123);
-    }
+    Ok(sum)
 }
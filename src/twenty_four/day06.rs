@@ -0,0 +1,151 @@
+use crate::{ ParsedSolution, Params, SolveError };
+use crate::util::point::{ Direction::*, Grid, Point };
+use std::collections::HashSet;
+use std::iter;
+
+pub const SOLUTION: ParsedSolution<Lab, usize, usize> = ParsedSolution {
+    params: &[],
+    parse,
+    part1,
+    part2,
+};
+
+// a position and the direction the guard is facing on it
+type State = (Point<i64>, Point<i64>);
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum Tile {
+    Floor,
+    Obstruction,
+    Guard,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '.' => Ok(Tile::Floor),
+            '#' => Ok(Tile::Obstruction),
+            '^' => Ok(Tile::Guard),
+            _ => Err(format!("Unexpected tile '{}'", c)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Lab {
+    grid: Grid<Tile>,
+    start: Point<i64>,
+}
+
+impl Lab {
+    // the guard's next state, turning right instead of walking into an
+    // obstruction, or None once she walks out of the lab
+    fn step(&self, (pos, dir): State, obstruction: Option<Point<i64>>) -> Option<State> {
+        let next = pos + dir;
+
+        match self.grid.get(next) {
+            None => None,
+            Some(Tile::Obstruction) => Some((pos, dir.rotate_right())),
+            Some(_) if obstruction == Some(next) => Some((pos, dir.rotate_right())),
+            Some(_) => Some((next, dir)),
+        }
+    }
+
+    // the states the guard passes through until she leaves the lab
+    fn patrol(&self) -> Result<Vec<State>, SolveError> {
+        let mut seen = HashSet::new();
+        let mut path = Vec::new();
+
+        for state in iter::successors(Some((self.start, North.into())), |&state| {
+            self.step(state, None)
+        }) {
+            if !seen.insert(state) {
+                return Err(SolveError::no_answer("The guard never leaves the lab"));
+            }
+
+            path.push(state);
+        }
+
+        Ok(path)
+    }
+
+    // whether the guard walks in circles from `state` with an obstruction
+    // added; only the states she turns in are remembered, as any loop
+    // must turn
+    fn loops(&self, mut state: State, obstruction: Point<i64>) -> bool {
+        let mut turns = HashSet::new();
+
+        while let Some(next) = self.step(state, Some(obstruction)) {
+            if next.0 == state.0 && !turns.insert(next) {
+                return true;
+            }
+
+            state = next;
+        }
+
+        false
+    }
+}
+
+fn parse(input: &str, _: &Params) -> Result<Lab, SolveError> {
+    let grid: Grid<Tile> = Grid::try_from(input)?;
+
+    if grid.width() == 0 {
+        return Err("Empty lab".into());
+    }
+
+    if grid.tiles.iter().any(|row| row.len() != grid.width()) {
+        return Err("Lab is not rectangular".into());
+    }
+
+    let start = grid
+        .tiles
+        .iter()
+        .enumerate()
+        .find_map(|(y, row)| {
+            let x = row.iter().position(|&tile| tile == Tile::Guard)?;
+            Some((x as i64, y as i64).into())
+        })
+        .ok_or("Guard not found")?;
+
+    Ok(Lab { grid, start })
+}
+
+fn part1(lab: &Lab) -> Result<usize, SolveError> {
+    let visited: HashSet<_> = lab.patrol()?.into_iter().map(|(pos, _)| pos).collect();
+
+    Ok(visited.len())
+}
+
+fn part2(lab: &Lab) -> Result<usize, SolveError> {
+    let path = lab.patrol()?;
+
+    // an obstruction only changes the path from where the guard first
+    // reaches it, so the walk can resume from the step before
+    let mut tried = HashSet::from([lab.start]);
+
+    let loops = path
+        .windows(2)
+        .filter(|states| {
+            let (before, (pos, _)) = (states[0], states[1]);
+            tried.insert(pos) && lab.loops(before, pos)
+        })
+        .count();
+
+    Ok(loops)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+    use crate::Params;
+
+    #[test]
+    fn test_parse() {
+        assert!(parse("..\n^.\n", &Params::new()).is_ok());
+        assert!(parse(".#.\n.\n..^\n", &Params::new()).is_err());
+        assert!(parse("", &Params::new()).is_err());
+    }
+}
@@ -1,4 +1,5 @@
-use crate::Solution;
+use crate::{ Solution, SolveError };
+use crate::util::parse;
 use std::ops::{ Add, Mul };
 
 pub const SOLUTION: Solution<u64, u64> = Solution { part1, part2 };
@@ -25,69 +26,52 @@ fn has_solution(
         })
 }
 
-fn parse_eqs(input: &str) -> Vec<(Vec<u64>, u64)> {
+fn parse_eqs(input: &str) -> Result<Vec<(Vec<u64>, u64)>, SolveError> {
     input
             .lines()
             .map(|l| {
-                let (n, ns) = l.split_once(':').unwrap();
-                let result = n.parse().unwrap();
-                let operands = ns
+                let (n, ns) = parse::split_once(input, l, ":")?;
+                let result = parse::token(input, n)?;
+                let operands: Vec<_> = ns
                     .split_whitespace()
-                    .map(|n| n.parse().unwrap())
-                    .collect();
+                    .map(|n| parse::token(input, n))
+                    .collect::<Result<_, _>>()?;
 
-                (operands, result)
+                if operands.is_empty() {
+                    return Err(SolveError::at(input, ns, "expected operands"));
+                }
+
+                Ok((operands, result))
             })
             .collect()
 }
 
-fn part1(input: &str) -> u64 {
-    let eqs = parse_eqs(input);
+fn part1(input: &str) -> Result<u64, SolveError> {
+    let eqs = parse_eqs(input)?;
     let ops = [u64::add, u64::mul];
 
-    eqs
+    let sum = eqs
         .iter()
         .filter(|(operands, result)|
             has_solution(&ops, &operands[1..], operands[0], *result)
         )
         .map(|(_, result)| result)
-        .sum()
+        .sum();
+
+    Ok(sum)
 }
 
-fn part2(input: &str) -> u64 {
-    let eqs = parse_eqs(input);
+fn part2(input: &str) -> Result<u64, SolveError> {
+    let eqs = parse_eqs(input)?;
     let ops = [u64::add, u64::mul, concat];
 
-    eqs
+    let sum = eqs
         .iter()
         .filter(|(operands, result)|
             has_solution(&ops, &operands[1..], operands[0], *result)
         )
         .map(|(_, result)| result)
-        .sum()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::{part1, part2};
+        .sum();
 
-    const TEST_INPUT: &str = "190: 10 19
-3267: 81 40 27
-83: 17 5
-156: 15 6
-7290: 6 8 6 15
-161011: 16 10 13
-192: 17 8 14
-21037: 9 7 18 13
-292: 11 6 16 20";
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 3749);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(TEST_INPUT), 11387);
-    }
+    Ok(sum)
 }
@@ -1,4 +1,4 @@
-use crate::Solution;
+use crate::{ Solution, SolveError };
 use std::hash::Hash;
 use std::ops::{ Add, Neg, Sub };
 use std::collections::{ HashMap, HashSet };
@@ -47,11 +47,16 @@ impl<T: Hash> AntennaMap<T> {
     }
 }
 
-impl From<&str> for AntennaMap<char> {
-    fn from(input: &str) -> Self {
+impl TryFrom<&str> for AntennaMap<char> {
+    type Error = SolveError;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
         let mut by_freq: HashMap<char, Vec<Point>> = HashMap::new();
 
-        let width = input.find('\n').unwrap() as isize;
+        let width = input
+            .find('\n')
+            .filter(|&width| width > 0)
+            .ok_or("Invalid map format")? as isize;
         let height = input.len() as isize / width - 1;
 
         for (y, l) in input.lines().enumerate() {
@@ -63,15 +68,15 @@ impl From<&str> for AntennaMap<char> {
             }
         }
 
-        Self { by_freq, width, height }
+        Ok(Self { by_freq, width, height })
     }
 }
 
-fn part1(input: &str) -> usize {
-    let antenna_map = AntennaMap::from(input);
+fn part1(input: &str) -> Result<usize, SolveError> {
+    let antenna_map = AntennaMap::try_from(input)?;
     let mut antinode_locations = HashSet::new();
 
-    for (_, antennae) in antenna_map.by_freq.iter() {
+    for antennae in antenna_map.by_freq.values() {
         for (i, a) in antennae.iter().enumerate() {
             for b in &antennae[i + 1..] {
                 let dir = *b - *a;
@@ -85,14 +90,14 @@ fn part1(input: &str) -> usize {
         }
     }
 
-    antinode_locations.len()
+    Ok(antinode_locations.len())
 }
 
-fn part2(input: &str) -> usize {
-    let antenna_map = AntennaMap::from(input);
+fn part2(input: &str) -> Result<usize, SolveError> {
+    let antenna_map = AntennaMap::try_from(input)?;
     let mut antinode_locations = HashSet::new();
 
-    for (_, antennae) in antenna_map.by_freq.iter() {
+    for antennae in antenna_map.by_freq.values() {
         for (i, a) in antennae.iter().enumerate() {
             for b in &antennae[i + 1..] {
                 let delta = *b - *a;
@@ -109,34 +114,5 @@ fn part2(input: &str) -> usize {
         }
     }
 
-    antinode_locations.len()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::{part1, part2};
-
-    const TEST_INPUT: &str = "............
-........0...
-.....0......
-.......0....
-....0.......
-......A.....
-............
-............
-........A...
-.........A..
-............
-............
-";
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 14);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(TEST_INPUT), 34);
-    }
+    Ok(antinode_locations.len())
 }
@@ -1,4 +1,4 @@
-use crate::Solution;
+use crate::{ Solution, SolveError };
 use std::collections::{ HashSet, LinkedList };
 
 pub const SOLUTION: Solution<usize, usize> = Solution { part1, part2 };
@@ -25,11 +25,25 @@ fn checksum<I>(chunks: I) -> usize where I: IntoIterator<Item = Chunk> {
     checksum
 }
 
-fn part1(input: &str) -> usize {
-    let mut list: Vec<Option<usize>> = input
-        .trim()
-        .chars()
-        .map(|c| c.to_digit(10).unwrap())
+fn parse_disk_map(input: &str) -> Result<Vec<usize>, SolveError> {
+    let disk_map = input.trim();
+
+    disk_map
+        .char_indices()
+        .map(|(i, c)| c
+            .to_digit(10)
+            .map(|d| d as usize)
+            .ok_or_else(|| {
+                let message = format!("'{}' is not a digit", c);
+                SolveError::at(input, &disk_map[i..], message)
+            })
+        )
+        .collect()
+}
+
+fn part1(input: &str) -> Result<usize, SolveError> {
+    let mut list: Vec<Option<usize>> = parse_disk_map(input)?
+        .into_iter()
         .enumerate()
         .flat_map(|(i, size)| {
             let node = if i % 2 == 0 { Some(i / 2) } else { None };
@@ -37,6 +51,10 @@ fn part1(input: &str) -> usize {
         })
         .collect();
 
+    if list.is_empty() {
+        return Ok(0);
+    }
+
     let mut i = 0;
     let mut j = list.len() - 1;
 
@@ -58,26 +76,23 @@ fn part1(input: &str) -> usize {
         i += 1;
     }
 
-    list
+    let checksum = list
         .iter()
         .enumerate()
         .filter_map(|(i, chunk)| chunk.map(|id| i * id))
-        .sum()
-}
+        .sum();
 
-fn part2(input: &str) -> usize {
-    let mut input = input.trim().to_string();
-    input.push('0');
+    Ok(checksum)
+}
 
-    let mut chunks: LinkedList<_> = input
-        .chars()
-        .map(|c| c.to_digit(10).unwrap() as usize)
-        .collect::<Vec<_>>()
+fn part2(input: &str) -> Result<usize, SolveError> {
+    let mut chunks: LinkedList<_> = parse_disk_map(input)?
         .chunks(2)
-        .collect::<Vec<_>>()
-        .iter()
         .enumerate()
-        .map(|(id, pair)| Chunk { id, size: pair[0], padding: pair[1] })
+        .map(|(id, pair)| {
+            let padding = pair.get(1).copied().unwrap_or(0);
+            Chunk { id, size: pair[0], padding }
+        })
         .collect();
 
     let mut defragmented = LinkedList::new();
@@ -116,22 +131,5 @@ fn part2(input: &str) -> usize {
         }
     }
 
-    checksum(defragmented)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::{part1, part2};
-
-    const TEST_INPUT: &str = "2333133121414131402";
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 1928);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(TEST_INPUT), 2858);
-    }
+    Ok(checksum(defragmented))
 }
@@ -1,4 +1,4 @@
-use crate::Solution;
+use crate::{ Solution, SolveError };
 use std::collections::HashSet;
 
 pub const SOLUTION: Solution<usize, usize> = Solution { part1, part2 };
@@ -80,16 +80,27 @@ impl TopoMap {
             .map(|next_pos| self.dfs2(next_pos))
             .sum()
     }
+}
+
+impl TryFrom<&str> for TopoMap {
+    type Error = SolveError;
 
-    fn from(input: &str) -> Self {
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
         let grid: Vec<Vec<_>> = input
             .trim()
             .lines()
             .map(|l| l
-                .chars()
-                .map(|c| c.to_digit(10).unwrap() as usize)
+                .char_indices()
+                .map(|(i, c)| c
+                    .to_digit(10)
+                    .map(|h| h as usize)
+                    .ok_or_else(|| {
+                        let message = format!("'{}' is not a height", c);
+                        SolveError::at(input, &l[i..], message)
+                    })
+                )
                 .collect())
-            .collect();
+            .collect::<Result<_, _>>()?;
 
         let trailheads: Vec<_> = grid
             .iter()
@@ -102,50 +113,30 @@ impl TopoMap {
             )
             .collect();
 
-        Self { grid, trailheads }
+        Ok(Self { grid, trailheads })
     }
 }
 
-fn part1(input: &str) -> usize {
-    let topo_map = TopoMap::from(input);
+fn part1(input: &str) -> Result<usize, SolveError> {
+    let topo_map = TopoMap::try_from(input)?;
 
-    topo_map
+    let score = topo_map
         .trailheads
         .iter()
         .map(|&pos| topo_map.dfs(pos, &mut HashSet::new()))
-        .sum()
+        .sum();
+
+    Ok(score)
 }
 
-fn part2(input: &str) -> usize {
-    let topo_map = TopoMap::from(input);
+fn part2(input: &str) -> Result<usize, SolveError> {
+    let topo_map = TopoMap::try_from(input)?;
 
-    topo_map
+    let rating = topo_map
         .trailheads
         .iter()
         .map(|&pos| topo_map.dfs2(pos))
-        .sum()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::{part1, part2};
-
-    const TEST_INPUT: &str = "89010123
-78121874
-87430965
-96549874
-45678903
-32019012
-01329801
-10456732";
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 36);
-    }
+        .sum();
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(TEST_INPUT), 81);
-    }
+    Ok(rating)
 }
@@ -1,7 +1,22 @@
-use crate::Solution;
+use crate::{ Param, Params, ParsedSolution, SolveError };
+use crate::util::parse;
 use std::collections::HashMap;
 
-pub const SOLUTION: Solution<u64, u64> = Solution { part1, part2 };
+pub const SOLUTION: ParsedSolution<Stones, u64, u64> = ParsedSolution {
+    params: &[
+        Param { name: "blinks1", default: 25, help: "blinks in part 1" },
+        Param { name: "blinks2", default: 75, help: "blinks in part 2" },
+    ],
+    parse,
+    part1,
+    part2,
+};
+
+#[derive(Debug)]
+pub struct Stones {
+    stones: Vec<u64>,
+    blinks: [u64; 2],
+}
 
 fn decimals(n: u64) -> u32 {
     n.checked_ilog10().map_or(1, |e| e + 1)
@@ -52,32 +67,21 @@ fn count_stones(stones: &[u64], blinks: u64) -> u64 {
     stone_count + splits
 }
 
-fn part1(input: &str) -> u64 {
-    let stones: Vec<_> = input
+fn parse(input: &str, params: &Params) -> Result<Stones, SolveError> {
+    let stones = input
         .split_whitespace()
-        .map(|s| s.parse().unwrap())
-        .collect();
-
-    count_stones(&stones, 25)
-}
+        .map(|s| parse::token(input, s))
+        .collect::<Result<_, _>>()?;
 
-fn part2(input: &str) -> u64 {
-    let stones: Vec<_> = input
-        .split_whitespace()
-        .map(|s| s.parse().unwrap())
-        .collect();
+    let blinks = [params.get("blinks1")?, params.get("blinks2")?];
 
-    count_stones(&stones, 75)
+    Ok(Stones { stones, blinks })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::part1;
-
-    const TEST_INPUT: &str = "125 17\n";
+fn part1(stones: &Stones) -> Result<u64, SolveError> {
+    Ok(count_stones(&stones.stones, stones.blinks[0]))
+}
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 55312);
-    }
+fn part2(stones: &Stones) -> Result<u64, SolveError> {
+    Ok(count_stones(&stones.stones, stones.blinks[1]))
 }
@@ -1,7 +1,15 @@
-use crate::Solution;
+use crate::{ ParsedSolution, Params, SolveError };
 use std::collections::{ HashSet, VecDeque };
 
-pub const SOLUTION: Solution<usize, usize> = Solution { part1, part2 };
+pub const SOLUTION: ParsedSolution<Prices, usize, usize> = ParsedSolution {
+    params: &[],
+    parse,
+    part1,
+    part2,
+};
+
+// total fence prices by perimeter and by number of sides
+type Prices = (usize, usize);
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 struct Point(usize, usize);
@@ -29,15 +37,6 @@ struct Garden {
 }
 
 impl Garden {
-    fn from(input: &str) -> Self {
-        let grid = input
-            .lines()
-            .map(|l| l.chars().collect())
-            .collect();
-
-        Self { grid }
-    }
-
     fn height(&self) -> usize {
         self.grid.len()
     }
@@ -159,75 +158,33 @@ impl Garden {
     }
 }
 
-fn part1(input: &str) -> usize {
-    let garden = Garden::from(input);
+impl TryFrom<&str> for Garden {
+    type Error = SolveError;
 
-    garden.total_fence_price().0
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let grid: Vec<Vec<char>> = input
+            .lines()
+            .map(|l| l.chars().collect())
+            .collect();
+
+        if grid.windows(2).any(|rows| rows[0].len() != rows[1].len()) {
+            return Err("Garden is not rectangular".into());
+        }
+
+        Ok(Self { grid })
+    }
 }
 
-fn part2(input: &str) -> usize {
-    let garden = Garden::from(input);
+fn parse(input: &str, _: &Params) -> Result<Prices, SolveError> {
+    let garden = Garden::try_from(input)?;
 
-    garden.total_fence_price().1
+    Ok(garden.total_fence_price())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::{part1, part2};
-
-    const TEST_INPUT_SMALL: &str = "AAAA
-BBCD
-BBCC
-EEEC
-";
-
-    const TEST_INPUT_XO: &str = "OOOOO
-OXOXO
-OOOOO
-OXOXO
-OOOOO
-";
-
-    const TEST_INPUT_E_SHAPED: &str = "EEEEE
-EXXXX
-EEEEE
-EXXXX
-EEEEE
-";
-
-    const TEST_INPUT_AB: &str = "AAAAAA
-AAABBA
-AAABBA
-ABBAAA
-ABBAAA
-AAAAAA
-";
-
-    const TEST_INPUT_LARGE: &str = "RRRRIICCFF
-RRRRIICCCF
-VVRRRCCFFF
-VVRCCCJFFF
-VVVVCJJCFE
-VVIVCCJJEE
-VVIIICJJEE
-MIIIIIJJEE
-MIIISIJEEE
-MMMISSJEEE
-";
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(TEST_INPUT_SMALL), 140);
-        assert_eq!(part1(TEST_INPUT_XO), 772);
-        assert_eq!(part1(TEST_INPUT_LARGE), 1930);
-    }
+fn part1(prices: &Prices) -> Result<usize, SolveError> {
+    Ok(prices.0)
+}
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(TEST_INPUT_SMALL), 80);
-        assert_eq!(part2(TEST_INPUT_XO), 436);
-        assert_eq!(part2(TEST_INPUT_E_SHAPED), 236);
-        assert_eq!(part2(TEST_INPUT_AB), 368);
-        assert_eq!(part2(TEST_INPUT_LARGE), 1206);
-    }
+fn part2(prices: &Prices) -> Result<usize, SolveError> {
+    Ok(prices.1)
 }
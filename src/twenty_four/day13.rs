@@ -1,4 +1,5 @@
-use crate::Solution;
+use crate::{ Solution, SolveError };
+use crate::util::parse;
 
 pub const SOLUTION: Solution<i64, i64> = Solution { part1, part2 };
 
@@ -21,62 +22,81 @@ fn solve(eqs: &[Vec<i64>]) -> Option<Vec<i64>> {
         .map(|(e_a, e_b)| e_a * b[0] - e_b * a[0])
         .collect();
 
+    // collinear buttons leave no single way to the prize
+    if r[1] == 0 {
+        return None;
+    }
+
     if r[2] % r[1] != 0 {
         return None;
     }
     let y = r[2] / r[1];
 
-    if (a[2] - a[1] * y) % a[0] != 0 {
+    // button A moves the claw along at least one axis, as r[1] isn't 0
+    let row = if a[0] != 0 { a } else { b };
+
+    if (row[2] - row[1] * y) % row[0] != 0 {
         return None;
     }
-    let x = (a[2] - a[1] * y) / a[0];
+    let x = (row[2] - row[1] * y) / row[0];
 
     Some(vec![x, y])
 }
 
-fn part1(input: &str) -> i64 {
-    let machines: Vec<Vec<_>> = input
+fn parse_machines(input: &str) -> Result<Vec<Vec<Vec<i64>>>, SolveError> {
+    input
         .trim()
         .split("\n\n")
-        .map(|machine| machine
-            .lines()
-            .map(|l| l
-                .split(',')
-                .map(|e| e.split_once(['+', '=']).unwrap().1.parse().unwrap())
-                .collect()
-            )
-            .collect()
-        )
+        .map(|machine| {
+            let rows: Vec<Vec<_>> = machine
+                .lines()
+                .map(|l| l
+                    .split(',')
+                    .map(|e| {
+                        let (_, n) = e
+                            .split_once(['+', '='])
+                            .ok_or_else(|| {
+                                SolveError::at(input, e, "expected '+' or '='")
+                            })?;
+
+                        parse::token(input, n)
+                    })
+                    .collect::<Result<_, _>>()
+                )
+                .collect::<Result<_, _>>()?;
+
+            if rows.len() != 3 || rows.iter().any(|row| row.len() != 2) {
+                return Err(SolveError::at(input, machine, "Invalid machine"));
+            }
+
+            Ok(rows)
+        })
+        .collect()
+}
+
+fn part1(input: &str) -> Result<i64, SolveError> {
+    let machines: Vec<Vec<_>> = parse_machines(input)?
+        .into_iter()
         .map(transpose)
         .collect();
 
-    machines
+    let tokens = machines
         .iter()
         .filter_map(|m| solve(m))
         .map(|coeffs| coeffs[0] * 3 + coeffs[1])
-        .sum()
+        .sum();
+
+    Ok(tokens)
 }
 
-fn part2(input: &str) -> i64 {
-    let machines: Vec<Vec<_>> = input
-        .trim()
-        .split("\n\n")
-        .map(|machine| machine
-            .lines()
-            .map(|l| l
-                .split(',')
-                .map(|e| e.split_once(['+', '=']).unwrap().1.parse().unwrap())
-                .collect()
-            )
-            .collect()
-        )
-        .map(|m: Vec<Vec<_>>| {
-            let mut eqs = m.clone();
+fn part2(input: &str) -> Result<i64, SolveError> {
+    let machines: Vec<Vec<_>> = parse_machines(input)?
+        .into_iter()
+        .map(|mut eqs| {
             let last_row = eqs.len() - 1;
-            let cols = eqs[last_row].len();
 
-            for i in 0..cols {
-                eqs[last_row][i] += 10000000000000;
+            for e in eqs[last_row].iter_mut() {
+                *e += 10000000000000;
             }
 
             eqs
@@ -84,36 +104,25 @@ fn part2(input: &str) -> i64 {
         .map(transpose)
         .collect();
 
-    machines
+    let tokens = machines
         .iter()
         .filter_map(|m| solve(m))
         .map(|coeffs| coeffs[0] * 3 + coeffs[1])
-        .sum()
+        .sum();
+
+    Ok(tokens)
 }
 
 #[cfg(test)]
 mod tests {
     use super::part1;
 
-    const TEST_INPUT: &str = "Button A: X+94, Y+34
-Button B: X+22, Y+67
-Prize: X=8400, Y=5400
-
-Button A: X+26, Y+66
-Button B: X+67, Y+21
-Prize: X=12748, Y=12176
-
-Button A: X+17, Y+86
-Button B: X+84, Y+37
-Prize: X=7870, Y=6450
-
-Button A: X+69, Y+23
-Button B: X+27, Y+71
-Prize: X=18641, Y=10279
-";
-
     #[test]
-    fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 480);
+    fn test_degenerate() {
+        let collinear = "Button A: X+1, Y+1\nButton B: X+1, Y+1\nPrize: X=5, Y=5";
+        assert_eq!(part1(collinear), Ok(0));
+
+        let upward = "Button A: X+0, Y+1\nButton B: X+1, Y+1\nPrize: X=5, Y=10";
+        assert_eq!(part1(upward), Ok(20));
     }
 }
@@ -1,10 +1,26 @@
-use crate::Solution;
+use crate::{ Param, Params, ParsedSolution, SolveError };
+use crate::util::parse;
 
 use std::ops::Add;
 use std::fmt::Display;
 use std::collections::HashSet;
 
-pub const SOLUTION: Solution<usize, usize> = Solution { part1, part2 };
+pub const SOLUTION: ParsedSolution<Restroom, usize, usize> = ParsedSolution {
+    params: &[
+        Param { name: "width", default: 101, help: "width of the room" },
+        Param { name: "height", default: 103, help: "height of the room" },
+        Param { name: "ticks", default: 100, help: "seconds in part 1" },
+    ],
+    parse,
+    part1,
+    part2,
+};
+
+#[derive(Debug)]
+pub struct Restroom {
+    map: RobotMap,
+    ticks: usize,
+}
 
 #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
 struct Point(isize, isize);
@@ -17,7 +33,7 @@ impl Add for Point {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct Robot {
     p: Point,
     v: Point,
@@ -34,7 +50,7 @@ impl Robot {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct RobotMap {
     robots: Vec::<Robot>,
     width: isize,
@@ -74,27 +90,35 @@ impl RobotMap {
             .len()
     }
 
-    fn from(input: &str, width: isize, height: isize) -> Self {
+    fn parse(
+        input: &str,
+        width: isize,
+        height: isize,
+    ) -> Result<Self, SolveError> {
         let robots = input
             .lines()
-            .map(|l| l
-                .split_whitespace()
-                .map(|s| {
-                    let (x, y) = s
-                        .split_once('=')
-                        .unwrap()
-                        .1
-                        .split_once(',')
-                        .unwrap();
-
-                    Point(x.parse().unwrap(), y.parse().unwrap())
-                })
-                .collect::<Vec<_>>()
-            )
-            .map(|r| Robot { p: r[0], v: r[1]  })
-            .collect();
+            .map(|l| {
+                let r = l
+                    .split_whitespace()
+                    .map(|s| {
+                        let (_, v) = parse::split_once(input, s, "=")?;
+                        let (x, y) = parse::split_once(input, v, ",")?;
+
+                        let x = parse::token(input, x)?;
+                        let y = parse::token(input, y)?;
+
+                        Ok(Point(x, y))
+                    })
+                    .collect::<Result<Vec<_>, SolveError>>()?;
+
+                match r[..] {
+                    [p, v] => Ok(Robot { p, v }),
+                    _ => Err(SolveError::at(input, l, "Invalid robot")),
+                }
+            })
+            .collect::<Result<_, _>>()?;
 
-        Self { robots, width, height }
+        Ok(Self { robots, width, height })
     }
 }
 
@@ -128,18 +152,30 @@ impl Display for RobotMap {
     }
 }
 
-fn part1(input: &str) -> usize {
-    let mut map = RobotMap::from(input, 101, 103);
+fn parse(input: &str, params: &Params) -> Result<Restroom, SolveError> {
+    let (width, height) = (params.get("width")?, params.get("height")?);
+
+    if width <= 0 || height <= 0 {
+        return Err("Room must have a positive size".into());
+    }
+
+    let map = RobotMap::parse(input, width, height)?;
+
+    Ok(Restroom { map, ticks: params.get("ticks")? })
+}
+
+fn part1(restroom: &Restroom) -> Result<usize, SolveError> {
+    let mut map = restroom.map.clone();
 
-    for _ in 0..100 {
+    for _ in 0..restroom.ticks {
         map.tick();
     }
 
-    map.safety_factor()
+    Ok(map.safety_factor())
 }
 
-fn part2(input: &str) -> usize {
-    let mut map = RobotMap::from(input, 101, 103);
+fn part2(restroom: &Restroom) -> Result<usize, SolveError> {
+    let mut map = restroom.map.clone();
     let mut ticks = 0;
 
     while map.distinct_positions() != map.robots.len() {
@@ -147,38 +183,8 @@ fn part2(input: &str) -> usize {
         ticks += 1;
     }
 
-    // print our tree
-    println!("{}", map);
+    // show our tree
+    diagnostic!("{}", map);
 
-    ticks
-}
-
-#[cfg(test)]
-mod tests {
-    use super::RobotMap;
-
-    const TEST_INPUT: &str = "p=0,4 v=3,-3
-p=6,3 v=-1,-3
-p=10,3 v=-1,2
-p=2,0 v=2,-1
-p=0,0 v=1,3
-p=3,0 v=-2,-2
-p=7,6 v=-1,-3
-p=3,0 v=-1,-2
-p=9,3 v=2,3
-p=7,3 v=-1,2
-p=2,4 v=2,-3
-p=9,5 v=-3,-3
-";
-
-    #[test]
-    fn test_part1() {
-        let mut map = RobotMap::from(TEST_INPUT, 11, 7);
-
-        for _ in 0..100 {
-            map.tick();
-        }
-
-        assert_eq!(map.safety_factor(), 12);
-    }
+    Ok(ticks)
 }
@@ -1,4 +1,5 @@
-use crate::Solution;
+use crate::{ Solution, SolveError };
+use crate::util::parse;
 use crate::util::point::Point;
 use std::collections::{HashMap, VecDeque};
 use std::fmt::Debug;
@@ -152,37 +153,30 @@ impl TryFrom<&str> for Warehouse {
     type Error = Box<dyn std::error::Error>;
 
     fn try_from(map_str: &str) -> Result<Self, Self::Error> {
-        let width = map_str.find('\n').ok_or("Invalid map format")?;
-        let height = map_str.len() / width;
+        let width = map_str.lines().map(str::len).max().unwrap_or(0);
+        let height = map_str.lines().count();
+
+        if width == 0 {
+            return Err("Empty map".into());
+        }
 
         let mut robot_pos = None;
+        let mut obstacles: HashMap<i64, HashMap<i64, Obstacle>> = HashMap::new();
+
+        for (y, l) in map_str.lines().enumerate() {
+            let y = y.try_into().map_err(|_| "Map too large")?;
+            let row = obstacles.entry(y).or_default();
+
+            for (x, c) in l.chars().enumerate() {
+                let x = x.try_into().map_err(|_| "Map too large")?;
 
-        let obstacles: HashMap<i64, HashMap<i64, Obstacle>> = map_str
-            .lines()
-            .enumerate()
-            .map(|(y, l)| {
-                let row = l
-                    .chars()
-                    .enumerate()
-                    .filter_map(|(x, c)| {
-                        let x = x.try_into().unwrap();
-                        let y = y.try_into().unwrap();
-
-                        if let Ok(o) = Obstacle::try_from(c) {
-                            Some((x, o))
-                        } else {
-                            if c == '@' {
-                                robot_pos = Some((x, y).into())
-                            }
-
-                            None
-                        }
-                    })
-                    .collect();
-
-                (y.try_into().unwrap(), row)
-            })
-            .collect();
+                if let Ok(o) = Obstacle::try_from(c) {
+                    row.insert(x, o);
+                } else if c == '@' {
+                    robot_pos = Some((x, y).into());
+                }
+            }
+        }
 
         let robot = robot_pos.ok_or("Robot not found")?;
 
@@ -203,12 +197,22 @@ impl Debug for Warehouse {
                         Obstacle::Box(BoxType::Right) => ']',
                 };
 
-                v[y as usize][x as usize] = c;
+                let tile = usize::try_from(y)
+                    .ok()
+                    .zip(usize::try_from(x).ok())
+                    .and_then(|(y, x)| v.get_mut(y)?.get_mut(x));
+
+                if let Some(tile) = tile {
+                    *tile = c;
+                }
             }
         }
 
-        let (r_x, r_y): (usize, usize) = self.robot.usized().unwrap();
-        v[r_y][r_x] = '@';
+        let (r_x, r_y): (usize, usize) = self.robot.usized().map_err(|_| std::fmt::Error)?;
+
+        if let Some(tile) = v.get_mut(r_y).and_then(|row| row.get_mut(r_x)) {
+            *tile = '@';
+        }
 
         let mut s = String::new();
 
@@ -221,99 +225,51 @@ impl Debug for Warehouse {
     }
 }
 
-fn part1(input: &str) -> usize {
-    let (map_str, moves_str) = input.split_once("\n\n").unwrap();
-    let mut warehouse = Warehouse::try_from(map_str).unwrap();
-    let moves: VecDeque<Point<i64>> = moves_str
-        .chars()
-        .filter(|&c| c != '\n')
-        .map(Point::try_from)
-        .collect::<Result<_, _>>()
-        .unwrap();
+fn parse_moves(
+    input: &str,
+    moves_str: &str,
+) -> Result<VecDeque<Point<i64>>, SolveError> {
+    moves_str
+        .char_indices()
+        .filter(|&(_, c)| c != '\n')
+        .map(|(i, c)| Point::try_from(c)
+            .map_err(|e| SolveError::at(input, &moves_str[i..], e))
+        )
+        .collect()
+}
+
+fn part1(input: &str) -> Result<usize, SolveError> {
+    let (map_str, moves_str) = parse::split_once(input, input, "\n\n")?;
+    let mut warehouse = Warehouse::try_from(map_str)?;
+    let moves = parse_moves(input, moves_str)?;
 
     for &m in moves.iter() {
         warehouse.attempt_move(m);
     }
 
-    warehouse.gps_sum().try_into().unwrap()
+    Ok(warehouse.gps_sum().try_into().map_err(|_| "GPS sum is negative")?)
 }
 
-fn part2(input: &str) -> usize {
-    let (map_str, moves_str) = input.split_once("\n\n").unwrap();
+fn part2(input: &str) -> Result<usize, SolveError> {
+    let (map_str, moves_str) = parse::split_once(input, input, "\n\n")?;
     let widened: String = map_str
-        .chars()
-        .map(|c| match c {
-            '#' => "##",
-            'O' => "[]",
-            '.' => "..",
-            '@' => "@,",
-            '\n' => "\n",
-            _ => panic!(),
+        .char_indices()
+        .map(|(i, c)| match c {
+            '#' => Ok("##"),
+            'O' => Ok("[]"),
+            '.' => Ok(".."),
+            '@' => Ok("@,"),
+            '\n' => Ok("\n"),
+            _ => Err(SolveError::at(input, &map_str[i..], "Invalid tile")),
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
-    let mut warehouse = Warehouse::try_from(widened.as_str()).unwrap();
-    let moves: VecDeque<Point<i64>> = moves_str
-        .chars()
-        .filter(|&c| c != '\n')
-        .map(Point::try_from)
-        .collect::<Result<_, _>>()
-        .unwrap();
+    let mut warehouse = Warehouse::try_from(widened.as_str())?;
+    let moves = parse_moves(input, moves_str)?;
 
     for &m in moves.iter() {
         warehouse.attempt_move(m);
     }
 
-    warehouse.gps_sum().try_into().unwrap()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::{part1, part2};
-
-    const TEST_INPUT_SMALL: &str = "########
-#..O.O.#
-##@.O..#
-#...O..#
-#.#.O..#
-#...O..#
-#......#
-########
-
-<^^>>>vv<v>>v<<
-";
-
-    const TEST_INPUT_LARGE: &str = "##########
-#..O..O.O#
-#......O.#
-#.OO..O.O#
-#..O@..O.#
-#O#..O...#
-#O..O..O.#
-#.OO.O.OO#
-#....O...#
-##########
-
-<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
-vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
-><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
-<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
-^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
-^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
->^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
-<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
-^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
-v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
-";
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(TEST_INPUT_SMALL), 2028);
-        assert_eq!(part1(TEST_INPUT_LARGE), 10092);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(TEST_INPUT_LARGE), 9021);
-    }
+    Ok(warehouse.gps_sum().try_into().map_err(|_| "GPS sum is negative")?)
 }
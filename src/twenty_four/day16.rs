@@ -1,12 +1,20 @@
-use crate::Solution;
+use crate::{ ParsedSolution, Params, SolveError };
 use crate::util::point::{ Direction::*, Point };
 use std::collections::{ BinaryHeap, HashMap, HashSet, VecDeque };
 use std::cmp::Reverse;
 
-pub const SOLUTION: Solution<Cost, usize> = Solution { part1, part2 };
+pub const SOLUTION: ParsedSolution<Paths, Cost, usize> = ParsedSolution {
+    params: &[],
+    parse,
+    part1,
+    part2,
+};
 
 type Cost = u64;
 
+// a node and the direction the reindeer is facing on it
+type State = (Point<i64>, Point<i64>);
+
 #[derive(Debug)]
 struct Graph {
     nodes: HashSet<Point<i64>>,
@@ -20,11 +28,11 @@ impl Graph {
         self.nodes.get(&neighbour).copied()
     }
 
-    fn dijkstra(&self, start: Point<i64>) -> (HashMap<(Point<i64>, Point<i64>), Cost>, HashMap<(Point<i64>, Point<i64>), HashSet<(Point<i64>, Point<i64>)>>) {
+    fn dijkstra(&self, start: Point<i64>) -> Paths {
         let mut queue = BinaryHeap::new();
         queue.push(Reverse((0, start, East.into())));
 
-        let mut costs: HashMap<(Point<i64>, Point<i64>), Cost> = self
+        let mut costs: HashMap<State, Cost> = self
             .nodes
             .iter()
             .flat_map(|&node|
@@ -34,8 +42,8 @@ impl Graph {
             )
             .collect();
 
-        let mut visited: HashSet<(Point<i64>, Point<i64>)> = HashSet::new();
-        let mut prev: HashMap<(Point<i64>, Point<i64>), HashSet<(Point<i64>, Point<i64>)>> = HashMap::new();
+        let mut visited: HashSet<State> = HashSet::new();
+        let mut prev: HashMap<State, HashSet<State>> = HashMap::new();
 
         costs.insert((start, East.into()), 0);
 
@@ -55,7 +63,10 @@ impl Graph {
                 let new_cost = cost + n_cost;
 
                 if let Some(neighbour) = neighbour_opt {
-                    let &current_cost = costs.get(&(neighbour, n_dir)).unwrap();
+                    let current_cost = costs
+                        .get(&(neighbour, n_dir))
+                        .copied()
+                        .unwrap_or(Cost::MAX);
 
                     if new_cost < current_cost {
                         costs.insert((neighbour, n_dir), new_cost);
@@ -70,31 +81,44 @@ impl Graph {
             }
         }
 
-        (costs, prev)
+        Paths { end: self.end, costs, prevs: prev }
     }
+}
 
-    fn shortest_path_len(&self) -> Cost {
-        let (costs, _) = self.dijkstra(self.start);
+// the cheapest costs from the start, and the states preceding each state on
+// its cheapest paths
+#[derive(Debug)]
+pub struct Paths {
+    end: Point<i64>,
+    costs: HashMap<State, Cost>,
+    prevs: HashMap<State, HashSet<State>>,
+}
 
-        costs
+impl Paths {
+    // the cost of the cheapest path to the end, if it can be reached
+    fn shortest_path_len(&self) -> Option<Cost> {
+        self
+            .costs
             .iter()
             .filter(|(&(node, _), _)| node == self.end)
             .map(|(_, &cost)| cost)
             .min()
-            .unwrap()
+            .filter(|&cost| cost != Cost::MAX)
     }
 
     fn shortest_paths(&self) -> usize {
-        let (costs, prevs) = self.dijkstra(self.start);
-
-        let mut ends: Vec<_> = costs
+        let mut ends: Vec<_> = self
+            .costs
             .iter()
             .filter(|(&(node, _), _)| node == self.end)
             .map(|((_, dir), cost)| (cost, dir))
             .collect();
 
         ends.sort();
-        let shortest_path_len = *ends[0].0;
+
+        let Some(&(&shortest_path_len, _)) = ends.first() else {
+            return 0;
+        };
 
         let dirs: HashSet<Point<i64>> = ends
             .iter()
@@ -102,7 +126,8 @@ impl Graph {
             .map(|(_, &dir)| dir)
             .collect();
 
-        let mut queue: VecDeque<(Point<i64>, Point<i64>)> = prevs
+        let mut queue: VecDeque<State> = self
+            .prevs
             .iter()
             .filter(|(&(node, dir), _)| node == self.end && dirs.contains(&dir))
             .flat_map(|(_, prev)| prev.clone())
@@ -116,7 +141,7 @@ impl Graph {
             }
             edges.insert((node, dir));
 
-            if let Some(to_queue) = prevs.get(&(node, dir)) {
+            if let Some(to_queue) = self.prevs.get(&(node, dir)) {
                 for &prev in to_queue {
                     queue.push_back(prev);
                 }
@@ -164,65 +189,23 @@ impl TryFrom<&str> for Graph {
     }
 }
 
-fn part1(input: &str) -> Cost {
-    let graph: Graph = Graph::try_from(input).unwrap();
-    graph.shortest_path_len()
-}
+fn parse(input: &str, _: &Params) -> Result<Paths, SolveError> {
+    let graph: Graph = Graph::try_from(input)?;
+    let paths = graph.dijkstra(graph.start);
+
+    if paths.shortest_path_len().is_none() {
+        return Err(SolveError::no_answer("End is unreachable"));
+    }
 
-fn part2(input: &str) -> usize {
-    let graph: Graph = Graph::try_from(input).unwrap();
-    graph.shortest_paths()
+    Ok(paths)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::{part1, part2};
-
-    const TEST_INPUT_1: &str = "###############
-#.......#....E#
-#.#.###.#.###.#
-#.....#.#...#.#
-#.###.#####.#.#
-#.#.#.......#.#
-#.#.#####.###.#
-#...........#.#
-###.#.#####.#.#
-#...#.....#.#.#
-#.#.#.###.#.#.#
-#.....#...#.#.#
-#.###.#.#.#.#.#
-#S..#.....#...#
-###############
-";
-
-    const TEST_INPUT_2: &str = "#################
- #...#...#...#..E#
- #.#.#.#.#.#.#.#.#
- #.#.#.#...#...#.#
- #.#.#.#.###.#.#.#
- #...#.#.#.....#.#
- #.#.#.#.#.#####.#
- #.#...#.#.#.....#
- #.#.#####.#.###.#
- #.#.#.......#...#
- #.#.###.#####.###
- #.#.#...#.....#.#
- #.#.#.#####.###.#
- #.#.#.........#.#
- #.#.#.#########.#
- #S#.............#
- #################
-";
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(TEST_INPUT_1), 7036);
-        assert_eq!(part1(TEST_INPUT_2), 11048);
-    }
+fn part1(paths: &Paths) -> Result<Cost, SolveError> {
+    paths
+        .shortest_path_len()
+        .ok_or(SolveError::no_answer("End is unreachable"))
+}
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(TEST_INPUT_1), 45);
-        assert_eq!(part2(TEST_INPUT_2), 64);
-    }
+fn part2(paths: &Paths) -> Result<usize, SolveError> {
+    Ok(paths.shortest_paths())
 }
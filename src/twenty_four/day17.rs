@@ -1,4 +1,5 @@
-use crate::Solution;
+use crate::{ Solution, SolveError };
+use std::error::Error;
 
 pub const SOLUTION: Solution<String, usize> = Solution { part1, part2 };
 
@@ -39,7 +40,7 @@ impl TryFrom<u8> for Literal {
     type Error = Box<dyn std::error::Error>;
 
     fn try_from(value: u8) -> Result<Self, Self::Error> {
-        if value < 7 {
+        if value < 8 {
             Ok(Self(value.into()))
         } else {
             Err("Invalid literal".into())
@@ -82,6 +83,9 @@ impl TryFrom<u8> for Combo {
     }
 }
 
+// how long a program may run before it is taken to never halt
+const MAX_STEPS: usize = 1 << 20;
+
 #[derive(Debug)]
 struct VM {
     regs: [i64; 3],
@@ -106,20 +110,23 @@ impl VM {
         }
     }
 
-    fn shr(&mut self, reg: Register, operand: Combo) {
+    fn shr(&mut self, reg: Register, operand: Combo) -> Result<(), Box<dyn Error>> {
         let numerator = self.read(Register(0));
         let denominator = self.deref_combo(operand);
 
-        let result = numerator >> denominator;
+        let shift = u32::try_from(denominator).map_err(|_| "Negative shift")?;
+        let result = numerator.checked_shr(shift).unwrap_or(0);
         self.write(reg, result);
+
+        Ok(())
     }
 
-    fn execute(&mut self, operation: Instruction) {
+    fn execute(&mut self, operation: Instruction) -> Result<(), Box<dyn Error>> {
         let mut jumped = false;
 
         match operation {
             Instruction::Adv(operand) => {
-                self.shr(Register(0), operand);
+                self.shr(Register(0), operand)?;
             },
             Instruction::Bxl(operand) => {
                 let b = self.read(Register(1));
@@ -135,7 +142,7 @@ impl VM {
                 let a = self.read(Register(0));
 
                 if a != 0 {
-                    self.ip = operand.0.try_into().expect("Invalid ip");
+                    self.ip = operand.0.try_into().map_err(|_| "Invalid jump target")?;
                     jumped = true;
                 }
             },
@@ -150,31 +157,41 @@ impl VM {
                 self.output.push(result);
             },
             Instruction::Bdv(operand) => {
-                self.shr(Register(1), operand);
+                self.shr(Register(1), operand)?;
             },
             Instruction::Cdv(operand) => {
-                self.shr(Register(2), operand);
+                self.shr(Register(2), operand)?;
             },
         }
 
         if !jumped {
             self.ip += 2;
         }
+
+        Ok(())
     }
 
-    fn step(&mut self) {
+    fn step(&mut self) -> Result<(), Box<dyn Error>> {
         let instruction: Instruction = (
             self.text[self.ip],
             self.text[self.ip + 1])
-            .try_into().expect("Invalid instruction");
+            .try_into()?;
 
-        self.execute(instruction);
+        self.execute(instruction)
     }
 
-    fn run(&mut self) {
-        while self.ip < self.text.len() {
-            self.step();
+    // runs until the instruction pointer leaves the program, which a jump to
+    // its last byte does too, as no operand follows
+    fn run(&mut self) -> Result<(), Box<dyn Error>> {
+        for _ in 0..MAX_STEPS {
+            if self.ip + 1 >= self.text.len() {
+                return Ok(());
+            }
+
+            self.step()?;
         }
+
+        Err(format!("Program did not halt within {} steps", MAX_STEPS).into())
     }
 
     fn reset(&mut self) {
@@ -214,11 +231,20 @@ impl TryFrom<&str> for VM {
                 .collect::<Result<Vec<_>, _>>()
             )
             .ok_or("Invalid program")?;
+        let text = program?;
+
+        if text.len() % 2 != 0 {
+            return Err("Program has a dangling opcode".into());
+        }
+
+        for pair in text.chunks(2) {
+            Instruction::try_from((pair[0], pair[1]))?;
+        }
 
         let vm = Self {
             regs: regs.try_into().map_err(|_| "Invalid number of registers")?,
             ip: 0,
-            text: program?,
+            text,
             output: vec!(),
         };
 
@@ -226,61 +252,49 @@ impl TryFrom<&str> for VM {
     }
 }
 
-fn part1(input: &str) -> String {
-    let mut vm = VM::try_from(input).unwrap();
-    vm.run();
-    vm.output()
-}
+// the lowest A, extending `a` by three bits at a time, for which the program
+// outputs itself; each output digit depends on the next three bits of A, the
+// last digit on the highest, so A is built up from the program's tail
+fn find_quine(vm: &mut VM, a: i64) -> Result<Option<i64>, Box<dyn Error>> {
+    let matched = vm.output.len();
 
-fn part2(input: &str) -> usize {
-    let mut vm = VM::try_from(input).unwrap();
-    let mut a: i64 = -1;
+    for bits in 0..8 {
+        let Some(candidate) = a.checked_mul(8).map(|a| a + bits) else {
+            return Ok(None);
+        };
 
-    for i in 0.. {
-        while vm.output.len() <= i || !vm.text.ends_with(&vm.output) {
-            a += 1;
+        vm.reset();
+        vm.regs[0] = candidate;
+        vm.run()?;
 
-            vm.reset();
-            vm.regs[0] = a;
-            vm.run();
+        if vm.output.len() != matched + 1 || !vm.text.ends_with(&vm.output) {
+            continue;
         }
 
-        if vm.output == vm.text {
-            break;
+        if vm.output.len() == vm.text.len() {
+            return Ok(Some(candidate));
         }
 
-        a *= 8;
-        a -= 1;
+        if let Some(a) = find_quine(vm, candidate)? {
+            return Ok(Some(a));
+        }
     }
 
-    a.try_into().unwrap()
+    Ok(None)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::{part1, part2};
-
-    const TEST_INPUT_1: &str = "Register A: 729
-Register B: 0
-Register C: 0
-
-Program: 0,1,5,4,3,0
-";
-
-    const TEST_INPUT_2: &str = "Register A: 2024
- Register B: 0
- Register C: 0
+fn part1(input: &str) -> Result<String, SolveError> {
+    let mut vm = VM::try_from(input)?;
+    vm.run()?;
+    Ok(vm.output())
+}
 
- Program: 0,3,5,4,3,0
-";
+fn part2(input: &str) -> Result<usize, SolveError> {
+    let mut vm = VM::try_from(input)?;
+    vm.output.clear();
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(TEST_INPUT_1), "4,6,3,5,6,3,5,2,1,0");
-    }
+    let a = find_quine(&mut vm, 0)?
+        .ok_or(SolveError::no_answer("No value of A makes the program output itself"))?;
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(TEST_INPUT_2), 117440);
-    }
+    Ok(a.try_into().map_err(|_| "Register A out of range")?)
 }
@@ -1,12 +1,26 @@
 use std::collections::{ HashMap, HashSet, VecDeque };
 use std::error::Error;
 
-use crate::Solution;
+use crate::{ Param, Params, ParsedSolution, SolveError };
 use crate::util::point::{ Direction::*, Point };
 
-pub const SOLUTION: Solution<usize, String> = Solution { part1, part2 };
+pub const SOLUTION: ParsedSolution<Ram, usize, (i64, i64)> = ParsedSolution {
+    params: &[
+        Param { name: "size", default: 70, help: "largest coordinate" },
+        Param { name: "bytes", default: 1024, help: "bytes fallen in part 1" },
+    ],
+    parse,
+    part1,
+    part2,
+};
 
 #[derive(Debug)]
+pub struct Ram {
+    memory: Memory,
+    bytes: usize,
+}
+
+#[derive(Clone, Debug)]
 struct Memory {
     fallen: HashSet<Point<u64>>,
     falling: VecDeque<Point<u64>>,
@@ -115,12 +129,18 @@ impl Memory {
     }
 
     fn part2(&mut self) -> Option<Point<u64>> {
-        let mut byte = self.falling.front().copied();
+        let mut byte = None;
 
         while let Some(path) = self.solve() {
-            while byte.is_some_and(|b|!path.contains(&b)) {
-                byte = self.falling.front().copied();
-                self.tick();
+            // only a byte landing on the path can cut it off
+            loop {
+                let fallen = self.falling.pop_front()?;
+                self.fallen.insert(fallen);
+                byte = Some(fallen);
+
+                if path.contains(&fallen) {
+                    break;
+                }
             }
         }
 
@@ -157,62 +177,39 @@ impl TryFrom<(&str, Point<u64>)> for Memory {
     }
 }
 
-fn part1(input: &str) -> usize {
-    let mut memory = Memory::try_from((input, (70, 70).into())).unwrap();
-    memory.advance(1024);
-    memory.part1().unwrap()
+fn parse(input: &str, params: &Params) -> Result<Ram, SolveError> {
+    let size = params.get("size")?;
+    let memory = Memory::try_from((input, (size, size).into()))?;
+
+    Ok(Ram { memory, bytes: params.get("bytes")? })
 }
 
-fn part2(input: &str) -> String {
-    let mut memory = Memory::try_from((input, (70, 70).into())).unwrap();
-    let byte = memory.part2().unwrap();
+fn part1(ram: &Ram) -> Result<usize, SolveError> {
+    let mut memory = ram.memory.clone();
+    memory.advance(ram.bytes);
+    memory.part1().ok_or(SolveError::no_answer("Exit is unreachable"))
+}
+
+fn part2(ram: &Ram) -> Result<(i64, i64), SolveError> {
+    let mut memory = ram.memory.clone();
+    let byte = memory
+        .part2()
+        .ok_or(SolveError::no_answer("No byte blocks the exit"))?;
+
+    let coordinate = |n: u64| i64::try_from(n).map_err(|e| e.to_string());
 
-    format!("{},{}", byte.x, byte.y)
+    Ok((coordinate(byte.x)?, coordinate(byte.y)?))
 }
 
 #[cfg(test)]
 mod tests {
     use super::Memory;
 
-    const TEST_INPUT: &str = "5,4
-4,2
-4,5
-3,0
-2,1
-6,3
-2,4
-1,5
-0,6
-3,3
-2,6
-5,1
-1,2
-5,5
-2,5
-6,5
-1,4
-0,4
-6,4
-1,1
-6,1
-1,0
-0,5
-1,6
-2,0
-";
-
-    #[test]
-    fn test_part1() {
-        let mut memory = Memory::try_from((TEST_INPUT, (6, 6).into())).unwrap();
-        memory.advance(12);
-        assert_eq!(memory.part1().unwrap(), 22);
-    }
-
     #[test]
-    fn test_part2() {
-        let mut memory = Memory::try_from((TEST_INPUT, (6, 6).into())).unwrap();
-        let byte = memory.part2().unwrap();
-
-        assert_eq!(format!("{},{}", byte.x, byte.y), "6,1".to_string());
+    fn test_unblocked() {
+        for input in ["", "1,2", "1,2\n0,1"] {
+            let mut memory = Memory::try_from((input, (6, 6).into())).unwrap();
+            assert_eq!(memory.part2(), None);
+        }
     }
 }
@@ -1,4 +1,5 @@
-use crate::Solution;
+use crate::{ Solution, SolveError };
+use crate::util::parse;
 
 use std::collections::HashMap;
 
@@ -62,8 +63,8 @@ impl Trie {
     }
 }
 
-fn parse_input(input: &str) -> (Trie, Vec<Vec<char>>) {
-    let (patterns_str, designs_str) = input.split_once("\n\n").unwrap();
+fn parse_input(input: &str) -> Result<(Trie, Vec<Vec<char>>), SolveError> {
+    let (patterns_str, designs_str) = parse::split_once(input, input, "\n\n")?;
 
     let patterns: Vec<_> = patterns_str
         .split(", ")
@@ -81,57 +82,27 @@ fn parse_input(input: &str) -> (Trie, Vec<Vec<char>>) {
         trie.insert(&pattern);
     }
 
-    (trie, designs)
+    Ok((trie, designs))
 }
 
-fn part1(input: &str) -> usize {
-    let (trie, designs) = parse_input(input);
+fn part1(input: &str) -> Result<usize, SolveError> {
+    let (trie, designs) = parse_input(input)?;
 
-    designs
+    let possible = designs
         .iter()
         .filter(|d| trie.count_possible(d, &trie, &mut HashMap::new()) > 0)
-        .count()
-}
+        .count();
 
-fn part2(input: &str) -> usize {
-    let (trie, designs) = parse_input(input);
+    Ok(possible)
+}
 
+fn part2(input: &str) -> Result<usize, SolveError> {
+    let (trie, designs) = parse_input(input)?;
 
-    designs
+    let arrangements = designs
         .iter()
         .map(|d| trie.count_possible(d, &trie, &mut HashMap::new()))
-        .sum()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::{part1, part2};
-
-    const TEST_INPUT: &str = "r, wr, b, g, bwu, rb, gb, br
-
-brwrr
-bggr
-gbbr
-rrbgbr
-ubwu
-bwurrg
-brgr
-bbrgwb
-";
+        .sum();
 
-    const BEEP: &str = "gr, g, rgr, r, rg, g
-
-rgrgr
-";
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 6);
-        assert_eq!(part1(BEEP), 1);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2(TEST_INPUT), 16);
-    }
+    Ok(arrangements)
 }
@@ -1,8 +1,25 @@
-use crate::Solution;
+use crate::{ Param, Params, ParsedSolution, SolveError };
 use crate::util::point::{ Direction::*, Grid, Point };
 use std::collections::{ HashMap, VecDeque };
 
-pub const SOLUTION: Solution<usize, usize> = Solution { part1, part2 };
+pub const SOLUTION: ParsedSolution<Race, usize, usize> = ParsedSolution {
+    params: &[
+        Param {
+            name: "min_savings",
+            default: 100,
+            help: "picoseconds a cheat must save to count",
+        },
+    ],
+    parse,
+    part1,
+    part2,
+};
+
+#[derive(Debug)]
+pub struct Race {
+    track: Racetrack,
+    min_savings: usize,
+}
 
 #[derive(Debug)]
 struct Racetrack {
@@ -143,46 +160,22 @@ impl TryFrom<&str> for Racetrack {
     }
 }
 
-fn part1(input: &str) -> usize {
-    let track = Racetrack::try_from(input).unwrap();
-    track.count_cheats(2, 100).unwrap()
-}
+fn parse(input: &str, params: &Params) -> Result<Race, SolveError> {
+    let track = Racetrack::try_from(input)?;
 
-fn part2(input: &str) -> usize {
-    let track = Racetrack::try_from(input).unwrap();
-    track.count_cheats(20, 100).unwrap()
+    Ok(Race { track, min_savings: params.get("min_savings")? })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::Racetrack;
-
-    const TEST_INPUT: &str = "###############
-#...#...#.....#
-#.#.#.#.#.###.#
-#S#...#.#.#...#
-#######.#.#.###
-#######.#.#...#
-#######.#.###.#
-###..E#...#...#
-###.#######.###
-#...###...#...#
-#.#####.#.###.#
-#.#...#.#.#...#
-#.#.#.#.#.#.###
-#...#...#...###
-###############
-";
-
-    #[test]
-    fn test_part1() {
-        let track = Racetrack::try_from(TEST_INPUT).unwrap();
-        assert_eq!(track.count_cheats(2, 64).unwrap(), 1);
-    }
+fn part1(race: &Race) -> Result<usize, SolveError> {
+    race
+        .track
+        .count_cheats(2, race.min_savings)
+        .ok_or(SolveError::no_answer("End is unreachable"))
+}
 
-    #[test]
-    fn test_part2() {
-        let track = Racetrack::try_from(TEST_INPUT).unwrap();
-        assert_eq!(track.count_cheats(20, 76).unwrap(), 3);
-    }
+fn part2(race: &Race) -> Result<usize, SolveError> {
+    race
+        .track
+        .count_cheats(20, race.min_savings)
+        .ok_or(SolveError::no_answer("End is unreachable"))
 }
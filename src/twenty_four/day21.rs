@@ -1,28 +1,227 @@
-use crate::Solution;
+use crate::{ ParsedSolution, Params, SolveError };
+use crate::util::parse;
+use crate::util::point::Point;
+use std::collections::HashMap;
+use std::iter;
 
-pub const SOLUTION: Solution<usize, usize> = Solution { part1, part2 };
+pub const SOLUTION: ParsedSolution<Codes, usize, usize> = ParsedSolution {
+    params: &[],
+    parse,
+    part1,
+    part2,
+};
 
-fn part1(input: &str) -> usize {
-    input.len()
+#[derive(Debug)]
+pub struct Codes {
+    codes: Vec<Code>,
 }
 
-fn part2(input: &str) -> usize {
-    input.len()
+// the keys of a code, and the number its digits make
+#[derive(Debug)]
+struct Code {
+    keys: String,
+    value: usize,
+}
+
+#[derive(Debug)]
+struct Keypad {
+    keys: HashMap<char, Point<i64>>,
+    // the robot arm panics if it is ever aimed here
+    gap: Point<i64>,
+}
+
+impl Keypad {
+    // rows of keys, top to bottom, with a space for the gap
+    fn new(rows: &[&str]) -> Self {
+        let mut keys = HashMap::new();
+        let mut gap = Point { x: -1, y: -1 };
+
+        for (y, row) in rows.iter().enumerate() {
+            for (x, key) in row.chars().enumerate() {
+                let pos = (x as i64, y as i64).into();
+
+                match key {
+                    ' ' => gap = pos,
+                    _ => { keys.insert(key, pos); },
+                }
+            }
+        }
+
+        Self { keys, gap }
+    }
+
+    fn numeric() -> Self {
+        Self::new(&["789", "456", "123", " 0A"])
+    }
+
+    fn directional() -> Self {
+        Self::new(&[" ^A", "<v>"])
+    }
+
+    // the presses on a directional keypad that move the arm from one key to
+    // another and press it, either all horizontal moves first or all
+    // vertical ones, unless that crosses the gap; zigzagging never helps, as
+    // each change of direction costs the robot above extra presses
+    fn paths(&self, from: char, to: char) -> Vec<String> {
+        let (from, to) = (self.keys[&from], self.keys[&to]);
+        let delta = to - from;
+
+        let moves = |key, n: i64| String::from(key).repeat(n.unsigned_abs() as usize);
+        let horizontal = moves(if delta.x < 0 { '<' } else { '>' }, delta.x);
+        let vertical = moves(if delta.y < 0 { '^' } else { 'v' }, delta.y);
+
+        let mut paths = Vec::new();
+
+        if (Point { x: to.x, y: from.y }) != self.gap {
+            paths.push(format!("{}{}A", horizontal, vertical));
+        }
+
+        if (Point { x: from.x, y: to.y }) != self.gap {
+            paths.push(format!("{}{}A", vertical, horizontal));
+        }
+
+        paths.dedup();
+        paths
+    }
+}
+
+// the directional keypads between the one typing a code and the human
+struct Robots {
+    keypad: Keypad,
+    // the fewest human presses to move from one key to another and press it,
+    // at a number of robots up the chain
+    costs: HashMap<(char, char, usize), usize>,
+}
+
+impl Robots {
+    fn new() -> Self {
+        Self { keypad: Keypad::directional(), costs: HashMap::new() }
+    }
+
+    // the fewest human presses for a robot `depth` keypads up the chain to
+    // type `sequence`, starting from A
+    fn cost(&mut self, sequence: &str, depth: usize) -> usize {
+        if depth == 0 {
+            return sequence.len();
+        }
+
+        iter::once('A')
+            .chain(sequence.chars())
+            .zip(sequence.chars())
+            .map(|(from, to)| self.press(from, to, depth))
+            .sum()
+    }
+
+    fn press(&mut self, from: char, to: char, depth: usize) -> usize {
+        if let Some(&cost) = self.costs.get(&(from, to, depth)) {
+            return cost;
+        }
+
+        let cost = self
+            .keypad
+            .paths(from, to)
+            .iter()
+            .map(|path| self.cost(path, depth - 1))
+            .min()
+            .unwrap_or(usize::MAX);
+
+        self.costs.insert((from, to, depth), cost);
+        cost
+    }
+}
+
+// the fewest human presses to type a code on the door's numeric keypad with
+// `robots` directional keypads in between
+fn complexity(codes: &[Code], robots: usize) -> Result<usize, SolveError> {
+    let numeric = Keypad::numeric();
+    let mut chain = Robots::new();
+
+    codes
+        .iter()
+        .try_fold(0usize, |sum, Code { keys, value }| {
+            let presses: usize = iter::once('A')
+                .chain(keys.chars())
+                .zip(keys.chars())
+                .map(|(from, to)| numeric
+                    .paths(from, to)
+                    .iter()
+                    .map(|path| chain.cost(path, robots))
+                    .min()
+                    .unwrap_or(usize::MAX)
+                )
+                .sum();
+
+            presses
+                .checked_mul(*value)
+                .and_then(|complexity| sum.checked_add(complexity))
+                .ok_or_else(|| "Complexity is too large".into())
+        })
+}
+
+fn parse(input: &str, _: &Params) -> Result<Codes, SolveError> {
+    let codes = input
+        .lines()
+        .map(|code| {
+            let digits = code
+                .strip_suffix('A')
+                .filter(|digits| !digits.is_empty())
+                .ok_or_else(|| SolveError::at(input, code, "expected digits then 'A'"))?;
+
+            if let Some(key) = digits.chars().find(|key| !key.is_ascii_digit()) {
+                return Err(SolveError::at(input, code, format!("No key '{}'", key)));
+            }
+
+            let value = parse::token(input, digits)?;
+
+            Ok(Code { keys: code.to_string(), value })
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Codes { codes })
+}
+
+fn part1(codes: &Codes) -> Result<usize, SolveError> {
+    complexity(&codes.codes, 2)
+}
+
+fn part2(codes: &Codes) -> Result<usize, SolveError> {
+    complexity(&codes.codes, 25)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{part1, part2};
+    use super::{parse, part1, Keypad, Robots};
+    use crate::Params;
+
+    #[test]
+    fn test_paths() {
+        let numeric = Keypad::numeric();
+        assert_eq!(numeric.paths('A', '1'), ["^<<A"]);
+        assert_eq!(numeric.paths('7', '0'), [">vvvA"]);
+        assert_eq!(numeric.paths('5', '9'), [">^A", "^>A"]);
+        assert_eq!(numeric.paths('A', 'A'), ["A"]);
 
-    const TEST_INPUT: &str = "";
+        let directional = Keypad::directional();
+        assert_eq!(directional.paths('A', '<'), ["v<<A"]);
+    }
 
     #[test]
-    fn test_part1() {
-        assert_eq!(part1(TEST_INPUT), 0);
+    fn test_cost() {
+        let mut robots = Robots::new();
+        assert_eq!(robots.cost("<A^A>^^AvvvA", 0), 12);
+        assert_eq!(robots.cost("<A^A>^^AvvvA", 1), 28);
+        assert_eq!(robots.cost("<A^A>^^AvvvA", 2), 68);
     }
 
     #[test]
-    fn test_part2() {
-        assert_eq!(part2(TEST_INPUT), TEST_INPUT.len());
+    fn test_parse() {
+        assert!(parse("029A\n", &Params::new()).is_ok());
+
+        for code in ["029", "A", "0A9A", "02#A", "99999999999999999999999A"] {
+            assert!(parse(code, &Params::new()).is_err(), "{}", code);
+        }
+
+        let long = parse("9999999999999999999A", &Params::new()).unwrap();
+        assert!(part1(&long).is_err());
     }
 }
@@ -0,0 +1,125 @@
+use crate::{ Param, Params, ParsedSolution, SolveError };
+use crate::util::parse;
+
+pub const SOLUTION: ParsedSolution<Market, u64, u64> = ParsedSolution {
+    params: &[
+        Param { name: "secrets", default: 2000, help: "new secret numbers per buyer per day" },
+    ],
+    parse,
+    part1,
+    part2,
+};
+
+const PRUNE: u64 = 1 << 24;
+
+// a window of four price changes, each in -9..=9, as four base-19 digits
+const WINDOWS: usize = 19 * 19 * 19 * 19;
+
+#[derive(Debug)]
+pub struct Market {
+    buyers: Vec<u64>,
+    secrets: usize,
+}
+
+/// The secret numbers a buyer generates after an initial one.
+#[derive(Clone, Copy, Debug)]
+pub struct Secrets {
+    secret: u64,
+}
+
+impl Secrets {
+    pub fn new(secret: u64) -> Self {
+        Self { secret }
+    }
+}
+
+impl Iterator for Secrets {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let mut secret = self.secret;
+
+        secret = (secret ^ (secret << 6)) % PRUNE;
+        secret = (secret ^ (secret >> 5)) % PRUNE;
+        secret = (secret ^ (secret << 11)) % PRUNE;
+
+        self.secret = secret;
+        Some(secret)
+    }
+}
+
+fn parse(input: &str, params: &Params) -> Result<Market, SolveError> {
+    let buyers = input
+        .split_whitespace()
+        .map(|s| parse::token(input, s))
+        .collect::<Result<_, _>>()?;
+
+    let secrets = params.get("secrets")?;
+
+    if secrets == 0 {
+        return Err("Buyers must generate at least one secret".into());
+    }
+
+    Ok(Market { buyers, secrets })
+}
+
+fn part1(market: &Market) -> Result<u64, SolveError> {
+    let sum = market
+        .buyers
+        .iter()
+        .map(|&secret| Secrets::new(secret).nth(market.secrets - 1).unwrap_or_default())
+        .sum();
+
+    Ok(sum)
+}
+
+fn part2(market: &Market) -> Result<u64, SolveError> {
+    // the bananas each window would earn across all buyers, and the last
+    // buyer to see each window, as only a buyer's first sighting sells
+    let mut bananas = vec![0; WINDOWS];
+    let mut seen_by = vec![usize::MAX; WINDOWS];
+
+    for (buyer, &secret) in market.buyers.iter().enumerate() {
+        let mut window = 0;
+        let mut price = secret % 10;
+
+        for (i, secret) in Secrets::new(secret).take(market.secrets).enumerate() {
+            let next = secret % 10;
+            let change = (next + 9 - price) as usize;
+
+            window = (window * 19 + change) % WINDOWS;
+            price = next;
+
+            if i >= 3 && seen_by[window] != buyer {
+                seen_by[window] = buyer;
+                bananas[window] += price;
+            }
+        }
+    }
+
+    Ok(bananas.into_iter().max().unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Secrets, SOLUTION};
+    use crate::Params;
+
+    #[test]
+    fn test_secrets() {
+        let secrets: Vec<_> = Secrets::new(123).take(10).collect();
+
+        assert_eq!(secrets, [
+            15887950, 16495136, 527345, 704524, 1553684,
+            12683156, 11100544, 12249484, 7753432, 5908254,
+        ]);
+    }
+
+    #[test]
+    fn test_parse() {
+        let params = |secrets| Params::new().with("secrets", secrets);
+
+        assert!(SOLUTION.parse("1\n10\n", &params(1)).is_ok());
+        assert!(SOLUTION.parse("1\n10\n", &params(0)).is_err());
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{ ParsedSolution, Params, SolveError };
+use crate::util::graph::Graph;
+use crate::util::parse;
+
+pub const SOLUTION: ParsedSolution<Graph, usize, String> = ParsedSolution {
+    params: &[],
+    parse,
+    part1,
+    part2,
+};
+
+fn parse(input: &str, _: &Params) -> Result<Graph, SolveError> {
+    let mut graph = Graph::new();
+
+    for line in input.lines() {
+        let (a, b) = parse::split_once(input, line, "-")?;
+        graph.add_edge(a, b);
+    }
+
+    Ok(graph)
+}
+
+// sets of three interconnected computers, one of which might be the chief
+// historian's
+fn part1(graph: &Graph) -> Result<usize, SolveError> {
+    let count = graph
+        .triangles()
+        .iter()
+        .filter(|triangle| triangle.iter().any(|&id| graph.name(id).starts_with('t')))
+        .count();
+
+    Ok(count)
+}
+
+// the password to the LAN party, the names of the largest set of
+// interconnected computers in order
+fn part2(graph: &Graph) -> Result<String, SolveError> {
+    let mut names: Vec<_> = graph.max_clique().into_iter().map(|id| graph.name(id)).collect();
+    names.sort();
+
+    Ok(names.join(","))
+}
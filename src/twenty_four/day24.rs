@@ -0,0 +1,286 @@
+use crate::{ ParsedSolution, Params, SolveError };
+use crate::util::parse;
+use std::collections::{ BTreeMap, HashMap };
+
+pub const SOLUTION: ParsedSolution<Circuit, u64, String> = ParsedSolution {
+    params: &[],
+    parse,
+    part1,
+    part2,
+};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum Op {
+    And,
+    Or,
+    Xor,
+}
+
+impl Op {
+    fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            Op::And => a && b,
+            Op::Or => a || b,
+            Op::Xor => a ^ b,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Gate {
+    inputs: [String; 2],
+    op: Op,
+    output: String,
+}
+
+/// A circuit of boolean gates, each driving its own wire, with some wires
+/// given initial values.
+#[derive(Debug)]
+pub struct Circuit {
+    initial: BTreeMap<String, bool>,
+    gates: Vec<Gate>,
+    // the gates reading each wire
+    readers: HashMap<String, Vec<usize>>,
+}
+
+impl Circuit {
+    fn parse(input: &str) -> Result<Self, SolveError> {
+        let (values, gates) = parse::split_once(input, input, "\n\n")?;
+
+        let mut initial = BTreeMap::new();
+
+        for line in values.lines() {
+            let (wire, value) = parse::split_once(input, line, ": ")?;
+            let value = match value {
+                "0" => false,
+                "1" => true,
+                _ => return Err(SolveError::at(input, value, "expected 0 or 1")),
+            };
+
+            initial.insert(wire.to_string(), value);
+        }
+
+        let mut circuit = Circuit { initial, gates: Vec::new(), readers: HashMap::new() };
+
+        for line in gates.lines() {
+            let (gate, output) = parse::split_once(input, line, " -> ")?;
+
+            let [a, op, b] = gate.split_whitespace().collect::<Vec<_>>()[..] else {
+                return Err(SolveError::at(input, gate, "expected <wire> <op> <wire>"));
+            };
+
+            let op = match op {
+                "AND" => Op::And,
+                "OR" => Op::Or,
+                "XOR" => Op::Xor,
+                _ => return Err(SolveError::at(input, op, format!("Unknown gate '{}'", op))),
+            };
+
+            let driven = circuit.initial.contains_key(output) ||
+                circuit.gates.iter().any(|gate| gate.output == output);
+
+            if driven {
+                return Err(SolveError::at(input, output, "wire is already driven"));
+            }
+
+            for wire in [a, b] {
+                circuit.readers.entry(wire.to_string()).or_default().push(circuit.gates.len());
+            }
+
+            circuit.gates.push(Gate {
+                inputs: [a.to_string(), b.to_string()],
+                op,
+                output: output.to_string(),
+            });
+        }
+
+        Ok(circuit)
+    }
+
+    // the value of every wire, evaluating each gate once both its inputs are
+    // known
+    fn evaluate(&self) -> Result<HashMap<&str, bool>, SolveError> {
+        let mut values: HashMap<&str, bool> = self
+            .initial
+            .iter()
+            .map(|(wire, &value)| (wire.as_str(), value))
+            .collect();
+
+        // the inputs of each gate still unknown
+        let mut unknown: Vec<usize> = self
+            .gates
+            .iter()
+            .map(|gate| gate
+                .inputs
+                .iter()
+                .filter(|wire| !values.contains_key(wire.as_str()))
+                .count()
+            )
+            .collect();
+
+        let mut ready: Vec<usize> = (0..self.gates.len()).filter(|&g| unknown[g] == 0).collect();
+
+        while let Some(g) = ready.pop() {
+            let Gate { inputs: [a, b], op, output } = &self.gates[g];
+
+            values.insert(output, op.apply(values[a.as_str()], values[b.as_str()]));
+
+            for &reader in self.readers.get(output).into_iter().flatten() {
+                unknown[reader] -= 1;
+
+                if unknown[reader] == 0 {
+                    ready.push(reader);
+                }
+            }
+        }
+
+        if values.len() < self.initial.len() + self.gates.len() {
+            return Err("Some gates are in a loop or read a wire nothing drives".into());
+        }
+
+        Ok(values)
+    }
+
+    // whether a wire is read by a gate of the given kind
+    fn read_by(&self, wire: &str, op: Op) -> bool {
+        self.readers
+            .get(wire)
+            .is_some_and(|readers| readers.iter().any(|&g| self.gates[g].op == op))
+    }
+
+    // the outputs of gates that don't fit a ripple-carry adder of the x and
+    // y inputs, where each bit i past the first is summed as
+    //
+    //     x XOR y -> s,  s XOR carry -> z,
+    //     x AND y -> a,  s AND carry -> b,  a OR b -> the next carry
+    //
+    // and the last carry is the highest bit of z
+    fn misplaced(&self) -> Vec<&str> {
+        let bits = self.initial.keys().filter(|wire| wire.starts_with('x')).count();
+        let last = format!("z{:02}", bits);
+
+        let is_input = |wire: &str| wire.starts_with('x') || wire.starts_with('y');
+        let is_first = |wire: &str| wire == "x00" || wire == "y00";
+
+        let mut misplaced: Vec<_> = self
+            .gates
+            .iter()
+            .filter(|Gate { inputs: [a, b], op, output }| {
+                let (a, b) = (a.as_str(), b.as_str());
+
+                match op {
+                    _ if *output == last => *op != Op::Or && bits > 1,
+                    Op::And | Op::Or if output.starts_with('z') => true,
+                    Op::Xor if !is_input(a) && !is_input(b) => !output.starts_with('z'),
+                    Op::Xor if is_first(a) => false,
+                    Op::Xor => !self.read_by(output, Op::Xor),
+                    Op::And if is_first(a) => false,
+                    Op::And => !self.read_by(output, Op::Or),
+                    Op::Or => false,
+                }
+            })
+            .map(|gate| gate.output.as_str())
+            .collect();
+
+        misplaced.sort();
+        misplaced
+    }
+}
+
+// the number with a bit from each wire starting with `prefix`, the wire
+// numbered 00 being the least significant, or an error if a set wire is
+// numbered past the 64 bits of the result
+fn number(values: &HashMap<&str, bool>, prefix: char) -> Result<u64, SolveError> {
+    values
+        .iter()
+        .filter(|(wire, &value)| value && wire.starts_with(prefix))
+        .filter_map(|(wire, _)| Some((wire, wire[1..].parse::<u32>().ok()?)))
+        .map(|(wire, bit)| {
+            1u64
+                .checked_shl(bit)
+                .ok_or_else(|| format!("Wire '{}' doesn't fit in 64 bits", wire).into())
+        })
+        .sum()
+}
+
+fn parse(input: &str, _: &Params) -> Result<Circuit, SolveError> {
+    Circuit::parse(input)
+}
+
+fn part1(circuit: &Circuit) -> Result<u64, SolveError> {
+    number(&circuit.evaluate()?, 'z')
+}
+
+fn part2(circuit: &Circuit) -> Result<String, SolveError> {
+    let misplaced = circuit.misplaced();
+
+    if misplaced.is_empty() {
+        return Err(SolveError::no_answer("The circuit already adds correctly"));
+    }
+
+    Ok(misplaced.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{number, Circuit};
+
+    // an adder of two `bits` bit numbers, with pairs of gate outputs swapped
+    fn adder(bits: usize, x: u64, y: u64, swaps: &[(&str, &str)]) -> Circuit {
+        let mut input = String::new();
+
+        for (name, n) in [("x", x), ("y", y)] {
+            for bit in 0..bits {
+                input += &format!("{}{:02}: {}\n", name, bit, n >> bit & 1);
+            }
+        }
+
+        input += "\nx00 XOR y00 -> z00\nx00 AND y00 -> c00\n";
+
+        for bit in 1..bits {
+            let carry = format!("c{:02}", bit - 1);
+            let next = match bit == bits - 1 {
+                true => format!("z{:02}", bits),
+                false => format!("c{:02}", bit),
+            };
+
+            input += &format!("x{0:02} XOR y{0:02} -> s{0:02}\n", bit);
+            input += &format!("x{0:02} AND y{0:02} -> a{0:02}\n", bit);
+            input += &format!("s{0:02} XOR {1} -> z{0:02}\n", bit, carry);
+            input += &format!("{1} AND s{0:02} -> b{0:02}\n", bit, carry);
+            input += &format!("a{0:02} OR b{0:02} -> {1}\n", bit, next);
+        }
+
+        for (a, b) in swaps {
+            input = input
+                .replace(&format!("-> {}\n", a), "-> swap\n")
+                .replace(&format!("-> {}\n", b), &format!("-> {}\n", a))
+                .replace("-> swap\n", &format!("-> {}\n", b));
+        }
+
+        Circuit::parse(&input).unwrap()
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let circuit = adder(6, 27, 45, &[]);
+        assert_eq!(number(&circuit.evaluate().unwrap(), 'z'), Ok(72));
+        assert!(circuit.misplaced().is_empty());
+
+        let looped = Circuit::parse("x00: 1\n\nx00 AND b -> a\nx00 OR a -> b\n").unwrap();
+        assert!(looped.evaluate().is_err());
+
+        assert!(Circuit::parse("x00: 1\n\nx00 NAND x00 -> a\n").is_err());
+        assert!(Circuit::parse("x00: 1\n\nx00 OR x00 -> x00\n").is_err());
+
+        let wide = Circuit::parse("z63: 1\nz64: 1\n\n").unwrap();
+        assert!(number(&wide.evaluate().unwrap(), 'z').is_err());
+    }
+
+    #[test]
+    fn test_misplaced() {
+        let circuit = adder(6, 27, 45, &[("s02", "a02"), ("z03", "b03"), ("z05", "a05")]);
+        assert_ne!(number(&circuit.evaluate().unwrap(), 'z'), Ok(72));
+        assert_eq!(circuit.misplaced(), ["a02", "a05", "b03", "s02", "z03", "z05"]);
+    }
+}
@@ -1,48 +1,27 @@
-use crate::Solver;
-
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day20;
-mod day21;
-
-pub fn get_solution(day: usize) -> &'static dyn Solver {
-    match day {
-        1 => &day01::SOLUTION,
-        2 => &day02::SOLUTION,
-        3 => &day03::SOLUTION,
-        4 => &day04::SOLUTION,
-        5 => &day05::SOLUTION,
-        7 => &day07::SOLUTION,
-        8 => &day08::SOLUTION,
-        9 => &day09::SOLUTION,
-        10 => &day10::SOLUTION,
-        11 => &day11::SOLUTION,
-        12 => &day12::SOLUTION,
-        13 => &day13::SOLUTION,
-        14 => &day14::SOLUTION,
-        15 => &day15::SOLUTION,
-        16 => &day16::SOLUTION,
-        17 => &day17::SOLUTION,
-        18 => &day18::SOLUTION,
-        19 => &day19::SOLUTION,
-        20 => &day20::SOLUTION,
-        21 => &day21::SOLUTION,
-        _ => unimplemented!(),
-    }
+puzzles! {
+    2024;
+    1 => day01, "Historian Hysteria";
+    2 => day02, "Red-Nosed Reports";
+    3 => day03, "Mull It Over";
+    4 => day04, "Ceres Search";
+    5 => day05, "Print Queue";
+    6 => day06, "Guard Gallivant";
+    7 => day07, "Bridge Repair";
+    8 => day08, "Resonant Collinearity";
+    9 => day09, "Disk Fragmenter";
+    10 => day10, "Hoof It";
+    11 => day11, "Plutonian Pebbles";
+    12 => day12, "Garden Groups";
+    13 => day13, "Claw Contraption";
+    14 => day14, "Restroom Redoubt";
+    15 => day15, "Warehouse Woes";
+    16 => day16, "Reindeer Maze";
+    17 => day17, "Chronospatial Computer";
+    18 => day18, "RAM Run";
+    19 => day19, "Linen Layout";
+    20 => day20, "Race Condition";
+    21 => day21, "Keypad Conundrum";
+    22 => day22, "Monkey Market";
+    23 => day23, "LAN Party";
+    24 => day24, "Crossed Wires";
 }
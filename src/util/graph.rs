@@ -0,0 +1,161 @@
+#![allow(dead_code)]
+
+use std::collections::{ BTreeSet, HashMap };
+
+/// An undirected graph whose nodes are named by strings, interned as ids
+/// numbered from 0 in the order they were added.
+#[derive(Default, Debug)]
+pub struct Graph {
+    ids: HashMap<String, usize>,
+    names: Vec<String>,
+    neighbours: Vec<BTreeSet<usize>>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// The id of a node, adding it if it is new.
+    pub fn intern(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+
+        let id = self.names.len();
+
+        self.ids.insert(name.to_string(), id);
+        self.names.push(name.to_string());
+        self.neighbours.push(BTreeSet::new());
+
+        id
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+
+    /// Connects two nodes, adding them if they are new.
+    pub fn add_edge(&mut self, a: &str, b: &str) {
+        let (a, b) = (self.intern(a), self.intern(b));
+
+        self.neighbours[a].insert(b);
+        self.neighbours[b].insert(a);
+    }
+
+    pub fn neighbours(&self, id: usize) -> &BTreeSet<usize> {
+        &self.neighbours[id]
+    }
+
+    pub fn is_adjacent(&self, a: usize, b: usize) -> bool {
+        self.neighbours[a].contains(&b)
+    }
+
+    /// Every set of three mutually connected nodes, once each, as ids in
+    /// ascending order.
+    pub fn triangles(&self) -> Vec<[usize; 3]> {
+        let mut triangles = Vec::new();
+
+        for a in 0..self.len() {
+            for &b in self.neighbours[a].range(a + 1..) {
+                for &c in self.neighbours[b].range(b + 1..) {
+                    if self.is_adjacent(a, c) {
+                        triangles.push([a, b, c]);
+                    }
+                }
+            }
+        }
+
+        triangles
+    }
+
+    /// A largest set of mutually connected nodes, as ids in ascending order.
+    pub fn max_clique(&self) -> Vec<usize> {
+        let mut best = BTreeSet::new();
+
+        let candidates = (0..self.len()).collect();
+        self.bron_kerbosch(BTreeSet::new(), candidates, BTreeSet::new(), &mut best);
+
+        best.into_iter().collect()
+    }
+
+    // Bron–Kerbosch with pivoting: extends the clique `r` with the
+    // `candidates`, skipping the nodes in `excluded` whose cliques have been
+    // searched already, and the pivot's neighbours, which are found through
+    // one of its non-neighbours (or the pivot itself) anyway
+    fn bron_kerbosch(
+        &self,
+        r: BTreeSet<usize>,
+        mut candidates: BTreeSet<usize>,
+        mut excluded: BTreeSet<usize>,
+        best: &mut BTreeSet<usize>,
+    ) {
+        if candidates.is_empty() {
+            if excluded.is_empty() && r.len() > best.len() {
+                *best = r;
+            }
+
+            return;
+        }
+
+        let pivot = candidates
+            .union(&excluded)
+            .max_by_key(|&&u| self.neighbours[u].intersection(&candidates).count())
+            .copied()
+            .unwrap_or_default();
+
+        let branches: Vec<_> = candidates.difference(&self.neighbours[pivot]).copied().collect();
+
+        for v in branches {
+            let neighbours = &self.neighbours[v];
+            let mut clique = r.clone();
+            clique.insert(v);
+
+            self.bron_kerbosch(
+                clique,
+                candidates.intersection(neighbours).copied().collect(),
+                excluded.intersection(neighbours).copied().collect(),
+                best,
+            );
+
+            candidates.remove(&v);
+            excluded.insert(v);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Graph;
+
+    #[test]
+    fn test_cliques() {
+        let mut graph = Graph::new();
+
+        for (a, b) in [("a", "b"), ("a", "c"), ("b", "c"), ("c", "d"), ("d", "e"), ("c", "e")] {
+            graph.add_edge(a, b);
+        }
+
+        assert_eq!(graph.len(), 5);
+        assert_eq!(graph.id("d"), Some(3));
+        assert_eq!(graph.triangles(), [[0, 1, 2], [2, 3, 4]]);
+
+        graph.add_edge("b", "d");
+        graph.add_edge("a", "d");
+
+        assert_eq!(graph.max_clique(), [0, 1, 2, 3]);
+        assert!(Graph::new().max_clique().is_empty());
+    }
+}
@@ -1 +1,3 @@
+pub mod graph;
+pub mod parse;
 pub mod point;
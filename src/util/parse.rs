@@ -0,0 +1,26 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::SolveError;
+
+/// Parses `s`, a slice of `input`, reporting failures at its position.
+pub fn token<T>(input: &str, s: &str) -> Result<T, SolveError>
+where T: FromStr, T::Err: Display
+{
+    s
+        .parse()
+        .map_err(|e| SolveError::at(input, s, format!("'{}': {}", s, e)))
+}
+
+/// Splits `s`, a slice of `input`, around the first occurrence of `delimiter`.
+pub fn split_once<'a>(
+    input: &str,
+    s: &'a str,
+    delimiter: &str,
+) -> Result<(&'a str, &'a str), SolveError> {
+    s
+        .split_once(delimiter)
+        .ok_or_else(|| {
+            SolveError::at(input, s, format!("expected '{}'", delimiter.escape_debug()))
+        })
+}
@@ -0,0 +1,282 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::error::Error;
+use std::fmt::{ self, Display };
+use std::fs;
+use std::path::{ Path, PathBuf };
+use std::str::FromStr;
+
+use crate::json::Value;
+use crate::{ Answer, Params, Puzzle, SolveError };
+
+/// Known answers to the real puzzle inputs, keyed by year, day and part.
+///
+/// They are stored as JSON, one line per day:
+///
+/// ```text
+/// {
+///   "2024": {
+///     "1": {"part1": 1882714, "part2": 19437052},
+///     "18": {"part1": 438, "part2": [26,22]}
+///   }
+/// }
+/// ```
+#[derive(PartialEq, Eq, Clone, Default, Debug)]
+pub struct Answers {
+    answers: BTreeMap<(u16, u8, u8), Answer>,
+}
+
+impl Answers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The answers file, `$AOC_ANSWERS` or `answers.json` in the working
+    /// directory.
+    pub fn path() -> PathBuf {
+        env::var_os("AOC_ANSWERS")
+            .map_or_else(|| PathBuf::from("answers.json"), PathBuf::from)
+    }
+
+    /// Loads answers from `path`, which may not exist yet.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+
+        let answers = fs::read_to_string(path)?
+            .parse()
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        Ok(answers)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        Ok(fs::write(path, self.to_string())?)
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: u8) -> Option<&Answer> {
+        self.answers.get(&(year, day, part))
+    }
+
+    pub fn set(&mut self, year: u16, day: u8, part: u8, answer: Answer) {
+        self.answers.insert((year, day, part), answer);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+
+    /// Whether any answer is known for the day.
+    pub fn contains_day(&self, year: u16, day: u8) -> bool {
+        self.answers.range((year, day, 0)..=(year, day, u8::MAX)).next().is_some()
+    }
+}
+
+impl FromStr for Answers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Value::Object(years) = s.parse()? else {
+            return Err("Expected an object of years".to_string());
+        };
+
+        let mut answers = Answers::new();
+
+        for (year_key, days) in &years {
+            let year = year_key
+                .parse()
+                .map_err(|_| format!("Invalid year '{}'", year_key))?;
+
+            let Value::Object(days) = days else {
+                return Err(format!("{}: expected an object of days", year));
+            };
+
+            for (day_key, parts) in days {
+                let day = day_key
+                    .parse()
+                    .map_err(|_| format!("{}: invalid day '{}'", year, day_key))?;
+
+                let error = |message| format!("{} day {}: {}", year, day, message);
+
+                let Value::Object(parts) = parts else {
+                    return Err(error("expected an object of parts".to_string()));
+                };
+
+                for (part_key, answer) in parts {
+                    let part = match part_key.as_str() {
+                        "part1" => 1,
+                        "part2" => 2,
+                        _ => return Err(error(format!("invalid part '{}'", part_key))),
+                    };
+
+                    let answer = Answer::from_json(answer)
+                        .ok_or_else(|| error(format!("invalid answer to part {}", part)))?;
+
+                    answers.set(year, day, part, answer);
+                }
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut years: BTreeMap<u16, BTreeMap<u8, Vec<_>>> = BTreeMap::new();
+
+        for (&(year, day, part), answer) in &self.answers {
+            years
+                .entry(year)
+                .or_default()
+                .entry(day)
+                .or_default()
+                .push((format!("part{}", part), answer.to_json()));
+        }
+
+        writeln!(f, "{{")?;
+
+        for (i, (year, days)) in years.iter().enumerate() {
+            writeln!(f, "  \"{}\": {{", year)?;
+
+            for (j, (day, parts)) in days.iter().enumerate() {
+                let parts: Vec<_> = parts
+                    .iter()
+                    .map(|(part, answer)| format!("\"{}\": {}", part, answer))
+                    .collect();
+
+                let comma = if j + 1 < days.len() { "," } else { "" };
+                writeln!(f, "    \"{}\": {{{}}}{}", day, parts.join(", "), comma)?;
+            }
+
+            let comma = if i + 1 < years.len() { "," } else { "" };
+            writeln!(f, "  }}{}", comma)?;
+        }
+
+        writeln!(f, "}}")
+    }
+}
+
+/// The outcome of checking one part against its known answer.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Outcome {
+    Pass(Answer),
+    Fail { expected: Answer, actual: Answer },
+    Error(SolveError),
+    /// Solved, but there is no known answer to check against.
+    MissingAnswer(Answer),
+    /// There is no cached input to solve.
+    MissingInput,
+}
+
+impl Outcome {
+    /// Whether a previously known answer is no longer produced.
+    pub fn is_regression(&self) -> bool {
+        matches!(self, Self::Fail { .. } | Self::Error(_))
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Check {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub outcome: Outcome,
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day {} part {}: ", self.year, self.day, self.part)?;
+
+        match &self.outcome {
+            Outcome::Pass(answer) => write!(f, "pass ({})", answer),
+            Outcome::Fail { expected, actual } => {
+                write!(f, "FAIL (expected {}, got {})", expected, actual)
+            },
+            Outcome::Error(e) => write!(f, "ERROR ({})", e),
+            Outcome::MissingAnswer(answer) => write!(f, "missing answer (got {})", answer),
+            Outcome::MissingInput => write!(f, "missing input"),
+        }
+    }
+}
+
+/// Solves `input` for `puzzle` with the default parameters and checks both
+/// parts against `answers`.
+pub fn verify(puzzle: &Puzzle, input: Option<&str>, answers: &Answers) -> [Check; 2] {
+    let Puzzle { year, day, .. } = *puzzle;
+    let check = |part, outcome| Check { year, day, part, outcome };
+
+    let Some(input) = input else {
+        return [check(1, Outcome::MissingInput), check(2, Outcome::MissingInput)];
+    };
+
+    let prepared = match puzzle.solver.prepare(input, &Params::new()) {
+        Ok(prepared) => prepared,
+        Err(e) => {
+            let e = e.in_day(day);
+            return [check(1, Outcome::Error(e.clone())), check(2, Outcome::Error(e))];
+        },
+    };
+
+    [(1, prepared.part1()), (2, prepared.part2())].map(|(part, answer)| {
+        let outcome = match (answer, answers.get(year, day, part)) {
+            (Err(e), _) => Outcome::Error(e.in_puzzle(day, part)),
+            (Ok(actual), None) => Outcome::MissingAnswer(actual),
+            (Ok(actual), Some(expected)) if actual == *expected => Outcome::Pass(actual),
+            (Ok(actual), Some(expected)) => {
+                Outcome::Fail { expected: expected.clone(), actual }
+            },
+        };
+
+        check(part, outcome)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{verify, Answer, Answers, Outcome};
+    use crate::get_puzzle;
+
+    const ANSWERS: &str = r#"{
+  "2024": {
+    "12": {"part1": 140, "part2": 81},
+    "18": {"part2": [6,1]}
+  }
+}
+"#;
+
+    #[test]
+    fn test_parse() {
+        let answers: Answers = ANSWERS.parse().unwrap();
+
+        assert_eq!(answers.get(2024, 12, 1), Some(&Answer::Integer(140)));
+        assert_eq!(answers.get(2024, 18, 2), Some(&Answer::Coordinate(6, 1)));
+        assert_eq!(answers.get(2024, 18, 1), None);
+        assert!(answers.contains_day(2024, 18));
+        assert!(!answers.contains_day(2024, 17));
+        assert_eq!(answers.to_string(), ANSWERS);
+        assert!(r#"{"2024": {"1": {"part3": 1}}}"#.parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        let answers: Answers = ANSWERS.parse().unwrap();
+        let puzzle = get_puzzle(2024, 12).unwrap();
+
+        let [part1, part2] = verify(puzzle, Some("AAAA\nBBCD\nBBCC\nEEEC\n"), &answers);
+
+        assert_eq!(part1.outcome, Outcome::Pass(Answer::Integer(140)));
+        assert_eq!(part2.outcome, Outcome::Fail {
+            expected: Answer::Integer(81),
+            actual: Answer::Integer(80),
+        });
+        assert_eq!(part2.to_string(), "2024 day 12 part 2: FAIL (expected 81, got 80)");
+
+        let [part1, _] = verify(puzzle, Some("AB\nC"), &answers);
+        assert!(part1.outcome.is_regression());
+
+        let [part1, _] = verify(puzzle, None, &Answers::new());
+        assert_eq!(part1.outcome, Outcome::MissingInput);
+    }
+}
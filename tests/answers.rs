@@ -0,0 +1,36 @@
+//! Checks every day with a cached input against the answers file, so a
+//! refactor that changes a known answer fails `cargo test`.
+//!
+//! Days without a cached input or a known answer are skipped, which makes
+//! this a no-op on a fresh checkout.
+
+use solutions::input::{ input_dir, InputStore };
+use solutions::{ Answers, Outcome };
+
+#[test]
+fn known_answers() {
+    let answers = Answers::load(&Answers::path()).unwrap();
+    let store = InputStore::new(input_dir());
+
+    let mut regressions = Vec::new();
+
+    for puzzle in solutions::puzzles() {
+        if !answers.contains_day(puzzle.year, puzzle.day) {
+            continue;
+        }
+
+        let input = store.get_cached(puzzle.year, puzzle.day).unwrap();
+
+        for check in solutions::verify(puzzle, input.as_deref(), &answers) {
+            match check.outcome {
+                Outcome::Pass(_) => (),
+                Outcome::MissingAnswer(_) | Outcome::MissingInput => {
+                    eprintln!("skipping {}", check);
+                },
+                _ => regressions.push(check.to_string()),
+            }
+        }
+    }
+
+    assert!(regressions.is_empty(), "\n{}", regressions.join("\n"));
+}
@@ -0,0 +1,21 @@
+//! Checks every day against the examples in `fixtures/`, so adding an
+//! example is a matter of adding its input and answers there.
+
+use solutions::fixtures;
+
+#[test]
+fn examples() {
+    let examples = fixtures::load_all(&fixtures::dir()).unwrap();
+    let mut failures = Vec::new();
+
+    for example in &examples {
+        for check in example.check() {
+            if check.outcome.is_regression() {
+                failures.push(format!("{} ({})", check, example.name));
+            }
+        }
+    }
+
+    assert!(!examples.is_empty());
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}